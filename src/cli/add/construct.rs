@@ -0,0 +1,63 @@
+use crate::cli::add::{component_name, Project};
+use crate::cli::init::TestTool;
+use crate::templates::generate::TemplateFile;
+
+use anyhow::Result;
+use clap::Args;
+
+const CONSTRUCT_TEMPLATE: &str = "add/construct.ts";
+const CONSTRUCT_TEST_TEMPLATE: &str = "add/construct.test.ts";
+const CONSTRUCTS_INDEX_FILE: &str = "lib/constructs/index.ts";
+
+#[derive(Debug, Args)]
+pub struct ConstructArgs {
+    /// Name of the construct, e.g. `StaticSite`
+    pub name: String,
+}
+
+/// Add a construct: `lib/constructs/<name>.ts` with its `<Name>Props`, a test unless the
+/// project has no test tool, and its re-export from `lib/constructs/index.ts`.
+///
+/// ### Parameters
+/// - `project` - Project to add the construct to
+/// - `args` - Arguments of the `add construct` command
+pub fn add_construct(project: &mut Project, args: &ConstructArgs) -> Result<()> {
+    // NOTE: eslint-cdk-plugin forbids the `Construct` suffix on construct class names.
+    let name = component_name(&args.name, "construct")?;
+    let context = project.template_context(&name);
+
+    let mut files = vec![project.new_file(
+        CONSTRUCT_TEMPLATE,
+        format!("lib/constructs/{}.ts", name.kebab),
+        &context,
+    )?];
+    if project.config.test_tool != TestTool::None {
+        files.push(project.new_file(
+            CONSTRUCT_TEST_TEMPLATE,
+            format!("test/constructs/{}.test.ts", name.kebab),
+            &context,
+        )?);
+    }
+
+    let export = format!("export * from './{}';", name.kebab);
+    let index = if project.path(CONSTRUCTS_INDEX_FILE).exists() {
+        project.read(CONSTRUCTS_INDEX_FILE)?
+    } else {
+        String::new()
+    };
+    if !index.lines().any(|line| line.trim() == export) {
+        let mut index = index;
+        if !index.is_empty() && !index.ends_with('\n') {
+            index.push('\n');
+        }
+        index.push_str(&export);
+        index.push('\n');
+        files.push(TemplateFile {
+            file_path: CONSTRUCTS_INDEX_FILE.to_string(),
+            content: index,
+            template: None,
+        });
+    }
+
+    project.write(files)
+}
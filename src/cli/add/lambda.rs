@@ -0,0 +1,202 @@
+use crate::cli::add::source_edit::{ensure_namespace_import, insert_in_constructor};
+use crate::cli::add::{component_name, Project};
+use crate::cli::init::TestTool;
+use crate::templates::generate::TemplateFile;
+use crate::templates::package_json::PackageJsonDocument;
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use dialoguer::Select;
+use std::io::IsTerminal;
+
+const NODEJS_HANDLER_TEMPLATE: &str = "add/lambda.ts";
+const NODEJS_TEST_TEMPLATE: &str = "add/lambda.test.ts";
+const PYTHON_HANDLER_TEMPLATE: &str = "add/lambda.py";
+const PYTHON_TEST_TEMPLATE: &str = "add/lambda_test.py";
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum LambdaRuntime {
+    /// TypeScript handler bundled by `NodejsFunction`
+    #[value(name = "nodejs")]
+    NodeJs,
+    /// Python handler bundled by `PythonFunction` (needs Docker)
+    Python,
+}
+
+#[derive(Debug, Args)]
+pub struct LambdaArgs {
+    /// Name of the function, e.g. `get-users`
+    pub name: String,
+
+    /// Language of the handler
+    #[arg(long, value_enum, default_value = "nodejs")]
+    pub runtime: LambdaRuntime,
+
+    /// Stack the function is added to, e.g. `api` for lib/api-stack.ts
+    /// (the project's stack by default)
+    #[arg(long, value_name = "NAME")]
+    pub stack: Option<String>,
+}
+
+/// Add a Lambda function: its handler under `lambda/<name>/` and its test under
+/// `test/lambda/`, the function in the constructor of a stack, and the packages it needs
+/// in `package.json`.
+///
+/// ### Parameters
+/// - `project` - Project to add the function to
+/// - `args` - Arguments of the `add lambda` command
+pub fn add_lambda(project: &mut Project, args: &LambdaArgs) -> Result<()> {
+    let name = component_name(&args.name, "function")?;
+    let context = project.template_context(&name);
+    let construct_id = format!("{}Function", name.pascal);
+
+    let stack_file_path = select_stack(project, args.stack.as_deref())?;
+    let stack = project.read(&stack_file_path)?;
+    if stack.contains(&format!("'{}'", construct_id)) {
+        anyhow::bail!("{} already defines {}", stack_file_path, construct_id);
+    }
+
+    let package_json = project.read("package.json")?;
+    let mut package_json = PackageJsonDocument::parse(&package_json)?;
+
+    let mut files = Vec::new();
+    let (stack, lambda) = ensure_namespace_import(&stack, "aws-cdk-lib/aws-lambda", "lambda");
+    let (stack, path) = ensure_namespace_import(&stack, "path", "path");
+    let (stack, function) = match args.runtime {
+        LambdaRuntime::NodeJs => {
+            files.push(project.new_file(
+                NODEJS_HANDLER_TEMPLATE,
+                format!("lambda/{}/index.ts", name.kebab),
+                &context,
+            )?);
+            if project.config.test_tool != TestTool::None {
+                files.push(project.new_file(
+                    NODEJS_TEST_TEMPLATE,
+                    format!("test/lambda/{}.test.ts", name.kebab),
+                    &context,
+                )?);
+            }
+            package_json.add_dev_dependency("@types/aws-lambda", "^8.10.147")?;
+            // NOTE: without esbuild, NodejsFunction bundles in Docker.
+            package_json.add_dev_dependency("esbuild", "^0.24.2")?;
+
+            let (stack, nodejs) =
+                ensure_namespace_import(&stack, "aws-cdk-lib/aws-lambda-nodejs", "lambdaNodejs");
+            let function = vec![
+                format!("new {}.NodejsFunction(this, '{}', {{", nodejs, construct_id),
+                format!(
+                    "  entry: {}.join(__dirname, '../lambda/{}/index.ts'),",
+                    path, name.kebab
+                ),
+                "  handler: 'handler',".to_string(),
+                format!("  runtime: {}.Runtime.NODEJS_20_X,", lambda),
+                "});".to_string(),
+            ];
+            (stack, function)
+        }
+        LambdaRuntime::Python => {
+            files.push(project.new_file(
+                PYTHON_HANDLER_TEMPLATE,
+                format!("lambda/{}/index.py", name.kebab),
+                &context,
+            )?);
+            files.push(project.new_file(
+                PYTHON_TEST_TEMPLATE,
+                format!("test/lambda/test_{}.py", name.snake),
+                &context,
+            )?);
+            // NOTE: alpha modules are released with every aws-cdk-lib version.
+            let cdk_version = package_json
+                .dependency("aws-cdk-lib")
+                .map(|version| version.trim_start_matches(['^', '~']).to_string())
+                .ok_or_else(|| anyhow::anyhow!("package.json does not depend on aws-cdk-lib"))?;
+            package_json.add_dependency(
+                "@aws-cdk/aws-lambda-python-alpha",
+                &format!("^{}-alpha.0", cdk_version),
+            )?;
+
+            let (stack, python) =
+                ensure_namespace_import(&stack, "@aws-cdk/aws-lambda-python-alpha", "python");
+            let function = vec![
+                format!("new {}.PythonFunction(this, '{}', {{", python, construct_id),
+                format!(
+                    "  entry: {}.join(__dirname, '../lambda/{}'),",
+                    path, name.kebab
+                ),
+                "  index: 'index.py',".to_string(),
+                "  handler: 'handler',".to_string(),
+                format!("  runtime: {}.Runtime.PYTHON_3_12,", lambda),
+                "});".to_string(),
+            ];
+            (stack, function)
+        }
+    };
+
+    let function = function.iter().map(String::as_str).collect::<Vec<_>>();
+    let stack = insert_in_constructor(&stack, &function).ok_or_else(|| {
+        anyhow::anyhow!(
+            "Cannot find the constructor of the stack in {}",
+            stack_file_path
+        )
+    })?;
+    files.push(TemplateFile {
+        file_path: stack_file_path,
+        content: stack,
+        template: None,
+    });
+    files.push(TemplateFile {
+        file_path: "package.json".to_string(),
+        content: package_json.to_json()?,
+        template: None,
+    });
+
+    project.write(files)?;
+    println!(
+        "Run `{} install` to install the new dependencies.",
+        project.config.package_manager.command()
+    );
+    if args.runtime == LambdaRuntime::Python {
+        println!(
+            "PythonFunction bundles the handler in Docker, which must be running for `cdk synth`."
+        );
+    }
+    Ok(())
+}
+
+/// Stack file the function is added to.
+///
+/// Without `--stack`, the user picks one of `lib/*-stack.ts` when there are several and
+/// prompts may be shown; otherwise the stack generated by `init` is used.
+fn select_stack(project: &Project, stack: Option<&str>) -> Result<String> {
+    if let Some(stack) = stack {
+        let file_path = format!("lib/{}-stack.ts", component_name(stack, "stack")?.kebab);
+        if !project.path(&file_path).is_file() {
+            anyhow::bail!("Stack {} does not exist", file_path);
+        }
+        return Ok(file_path);
+    }
+
+    let default = project.stack_file_path();
+    let mut stacks = std::fs::read_dir(project.path("lib"))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|file_name| file_name.ends_with("-stack.ts"))
+        .map(|file_name| format!("lib/{}", file_name))
+        .collect::<Vec<_>>();
+    stacks.sort();
+
+    if stacks.len() > 1 && std::io::stdin().is_terminal() {
+        let index = Select::new()
+            .with_prompt("Choose the stack to add the function to")
+            .items(&stacks)
+            .default(
+                stacks
+                    .iter()
+                    .position(|stack| stack == &default)
+                    .unwrap_or(0),
+            )
+            .interact()?;
+        return Ok(stacks[index].clone());
+    }
+    Ok(default)
+}
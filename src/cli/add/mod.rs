@@ -0,0 +1,405 @@
+pub mod construct;
+pub mod lambda;
+pub mod pipeline;
+pub mod source_edit;
+pub mod stack;
+pub mod stage;
+
+use crate::cli::init::{template_loader, ProjectConfig};
+use crate::cli::user_config::load_user_config;
+use crate::templates::generate::TemplateFile;
+use crate::templates::loader::TemplateLoader;
+use crate::templates::manifest::{Manifest, MANIFEST_FILE_NAME};
+use crate::templates::naming::Naming;
+use crate::templates::render::{render_template, TemplateContext};
+use crate::templates::validate::validate_template_files;
+use crate::templates::write::write_template_files;
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use dialoguer::Confirm;
+use regex::Regex;
+use serde::Serialize;
+use std::io::IsTerminal;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Args)]
+pub struct AddArgs {
+    /// Local template directory, instead of the one recorded in .ts-cdk.toml
+    #[arg(long, value_name = "DIR", global = true)]
+    pub template_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: AddCommand,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum AddCommand {
+    /// Add a stack in lib/ and instantiate it in bin/
+    Stack(stack::StackArgs),
+    /// Add a reusable construct in lib/constructs/
+    Construct(construct::ConstructArgs),
+    /// Add a Lambda function in lambda/ and wire it into a stack
+    Lambda(lambda::LambdaArgs),
+    /// Add a stage grouping the stacks and deploy it to each environment
+    Stage(stage::StageArgs),
+    /// Add a CDK Pipelines stack deploying the stage to each environment
+    Pipeline(pipeline::PipelineArgs),
+}
+
+/// Templates used by the `add` commands, besides those of `TEMPLATE_REGISTRY`.
+pub static ADD_TEMPLATES: &[&str] = &[
+    "add/stack.test.ts",
+    "add/construct.ts",
+    "add/construct.test.ts",
+    "add/lambda.ts",
+    "add/lambda.test.ts",
+    "add/lambda.py",
+    "add/lambda_test.py",
+    "add/stage.ts",
+    "add/stage.test.ts",
+    "add/stage.bin.ts",
+    "add/environments.ts",
+    "add/pipeline.ts",
+    "add/pipeline.test.ts",
+    "add/pipeline.bin.ts",
+];
+
+/// Every template of the `add` commands with the variables it is rendered with for a
+/// sample component, so that `template check` can render and validate them.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig of the checked combination
+///
+/// ### Returns
+/// - `Vec<(&str, serde_json::Value)>` - Template paths with their variables
+pub fn sample_contexts(config: &ProjectConfig) -> Result<Vec<(&'static str, serde_json::Value)>> {
+    let context = TemplateContext {
+        name: Naming::new("sample"),
+        ..TemplateContext::new(config)
+    };
+    let mut samples = stage::sample_contexts(&context)?;
+    samples.extend(pipeline::sample_contexts(&context)?);
+    let value = serde_json::to_value(&context)?;
+    for &template_path in ADD_TEMPLATES {
+        if !samples.iter().any(|(path, _)| *path == template_path) {
+            samples.push((template_path, value.clone()));
+        }
+    }
+    Ok(samples)
+}
+
+/// Run an `add` command in the project containing the current directory.
+///
+/// ### Parameters
+/// - `args` - Arguments of the `add` command
+pub fn add(args: &AddArgs) -> Result<()> {
+    let mut project = Project::discover(args.template_dir.as_deref())?;
+    match &args.command {
+        AddCommand::Stack(args) => stack::add_stack(&mut project, args),
+        AddCommand::Construct(args) => construct::add_construct(&mut project, args),
+        AddCommand::Lambda(args) => lambda::add_lambda(&mut project, args),
+        AddCommand::Stage(args) => stage::add_stage(&mut project, args),
+        AddCommand::Pipeline(args) => pipeline::add_pipeline(&mut project, args),
+    }
+}
+
+/// A class exported by a file in lib/.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassRef {
+    pub class_name: String,
+    /// Path of the file relative to the project root, e.g. `lib/api-stack.ts`
+    pub file_path: String,
+    /// Module relative to lib/, e.g. `api-stack`
+    pub module: String,
+}
+
+/// A project generated by `init`.
+#[derive(Debug)]
+pub struct Project {
+    /// Choices the project was created with; `target_dir_path` is the project root
+    pub config: ProjectConfig,
+    pub manifest: Manifest,
+    pub loader: TemplateLoader,
+}
+
+impl Project {
+    /// Find the project containing the current directory by looking for `.ts-cdk.toml`
+    /// in it and its parents.
+    ///
+    /// ### Parameters
+    /// - `template_dir` - Template directory replacing the one recorded in `.ts-cdk.toml`
+    ///
+    /// ### Returns
+    /// - `Project` - Project
+    pub fn discover(template_dir: Option<&Path>) -> Result<Self> {
+        let current_dir = std::env::current_dir()?;
+        let root = current_dir
+            .ancestors()
+            .find(|dir| dir.join(MANIFEST_FILE_NAME).is_file())
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "No {} found in {} or its parents; run this command in a project created by `ts-cdk init`",
+                    MANIFEST_FILE_NAME,
+                    current_dir.display()
+                )
+            })?;
+        Project::open(root, template_dir)
+    }
+
+    /// Open the project at `root`, loading templates from those it was generated from.
+    ///
+    /// A recorded template directory that does not exist (e.g. in another checkout) is
+    /// skipped with a warning, leaving the user configuration's and the embedded templates.
+    ///
+    /// ### Parameters
+    /// - `root` - Root directory of the project
+    /// - `template_dir` - Template directory replacing the one recorded in `.ts-cdk.toml`
+    pub fn open(root: &Path, template_dir: Option<&Path>) -> Result<Self> {
+        let manifest = Manifest::load(root)?;
+        let user_config = load_user_config()?;
+
+        let mut sources = manifest.templates.clone();
+        sources.template_dir = match (template_dir, &sources.template_dir) {
+            (Some(dir), _) => Some(dir.to_path_buf()),
+            (None, Some(dir)) if root.join(dir).is_dir() => Some(root.join(dir)),
+            (None, Some(dir)) => {
+                eprintln!(
+                    "Warning: template directory '{}' recorded in {} does not exist; using the other templates (pass --template-dir to choose one)",
+                    dir.display(),
+                    MANIFEST_FILE_NAME
+                );
+                None
+            }
+            (None, None) => None,
+        };
+        let loader = template_loader(&sources, &user_config)
+            .context("Failed to load the templates the project was generated from")?;
+        Ok(Project {
+            config: manifest.project.clone(),
+            manifest,
+            loader,
+        })
+    }
+
+    /// Path of a project file on disk.
+    pub fn path(&self, file_path: &str) -> PathBuf {
+        self.config.target_dir_path.join(file_path)
+    }
+
+    /// Read a project file.
+    pub fn read(&self, file_path: &str) -> Result<String> {
+        std::fs::read_to_string(self.path(file_path))
+            .with_context(|| format!("Failed to read {}", file_path))
+    }
+
+    /// Path of the entry point of the app, `bin/<project>.ts`.
+    pub fn bin_file_path(&self) -> String {
+        format!("bin/{}.ts", TemplateContext::new(&self.config).name.kebab)
+    }
+
+    /// Path of the stack generated by `init`, `lib/<project>-stack.ts`.
+    pub fn stack_file_path(&self) -> String {
+        format!(
+            "lib/{}-stack.ts",
+            TemplateContext::new(&self.config).name.kebab
+        )
+    }
+
+    /// Classes extending `base_class` exported by the files of lib/ ending with `file_suffix`.
+    ///
+    /// ### Parameters
+    /// - `file_suffix` - End of the file names, e.g. `-stack.ts`
+    /// - `base_class` - Base class, e.g. `Stack` (also matches `cdk.Stack`)
+    ///
+    /// ### Returns
+    /// - `Vec<ClassRef>` - Classes, sorted by file path
+    pub fn find_classes(&self, file_suffix: &str, base_class: &str) -> Result<Vec<ClassRef>> {
+        let pattern = Regex::new(&format!(
+            r"export\s+class\s+([A-Za-z_$][\w$]*)\s+extends\s+(?:[\w$]+\.)?{}\b",
+            regex::escape(base_class)
+        ))?;
+        let lib_dir = self.path("lib");
+        if !lib_dir.is_dir() {
+            return Ok(Vec::new());
+        }
+
+        let mut classes = Vec::new();
+        for entry in std::fs::read_dir(&lib_dir)? {
+            let file_name = entry?.file_name().to_string_lossy().into_owned();
+            if !file_name.ends_with(file_suffix) {
+                continue;
+            }
+            let module = file_name.trim_end_matches(".ts");
+            let file_path = format!("lib/{}", file_name);
+            let source = self.read(&file_path)?;
+            for captures in pattern.captures_iter(&source) {
+                classes.push(ClassRef {
+                    class_name: captures[1].to_string(),
+                    file_path: file_path.clone(),
+                    module: module.to_string(),
+                });
+            }
+        }
+        classes.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        Ok(classes)
+    }
+
+    /// Template context of the project with `name` replaced by the added component's name.
+    pub fn template_context(&self, name: &Naming) -> TemplateContext {
+        TemplateContext {
+            name: name.clone(),
+            ..TemplateContext::new(&self.config)
+        }
+    }
+
+    /// Render a template of the `add` commands into a file that must not exist yet.
+    ///
+    /// ### Parameters
+    /// - `template_path` - Path of the template, e.g. `add/construct.ts`
+    /// - `file_path` - Path of the file relative to the project root
+    /// - `context` - Variables of the template, usually a `TemplateContext`
+    ///
+    /// ### Returns
+    /// - `TemplateFile` - Rendered file
+    pub fn new_file<S: Serialize>(
+        &self,
+        template_path: &str,
+        file_path: String,
+        context: &S,
+    ) -> Result<TemplateFile> {
+        if self.path(&file_path).exists() {
+            anyhow::bail!("{} already exists", file_path);
+        }
+        self.render_file(template_path, file_path, context)
+    }
+
+    /// Render a template of the `add` commands into a file, replacing it if it exists.
+    ///
+    /// ### Parameters
+    /// - `template_path` - Path of the template, e.g. `add/stage.bin.ts`
+    /// - `file_path` - Path of the file relative to the project root
+    /// - `context` - Variables of the template, usually a `TemplateContext`
+    ///
+    /// ### Returns
+    /// - `TemplateFile` - Rendered file
+    pub fn render_file<S: Serialize>(
+        &self,
+        template_path: &str,
+        file_path: String,
+        context: &S,
+    ) -> Result<TemplateFile> {
+        let source = self.loader.load(template_path)?;
+        Ok(TemplateFile {
+            file_path,
+            content: render_template(template_path, &source, context)?,
+            template: Some(template_path.to_string()),
+        })
+    }
+
+    /// Make sure a file generated by ts-cdk may be replaced: ask first when it was changed
+    /// since ts-cdk wrote it, or fail without a terminal unless `force` is set.
+    ///
+    /// ### Parameters
+    /// - `file_path` - Path of the file relative to the project root
+    /// - `question` - Question asked after saying the file was changed
+    /// - `force` - Replace the file without asking
+    pub fn confirm_replace(&self, file_path: &str, question: &str, force: bool) -> Result<()> {
+        let content = self.read(file_path)?;
+        if force || self.manifest.is_unmodified(file_path, &content) {
+            return Ok(());
+        }
+        let confirmed = std::io::stdin().is_terminal()
+            && Confirm::new()
+                .with_prompt(format!(
+                    "{} was changed since ts-cdk wrote it. {}",
+                    file_path, question
+                ))
+                .default(false)
+                .interact()?;
+        if !confirmed {
+            anyhow::bail!(
+                "{} was changed since ts-cdk wrote it; re-run with --force to replace it",
+                file_path
+            );
+        }
+        Ok(())
+    }
+
+    /// Write new and updated files, then record their checksums in `.ts-cdk.toml`.
+    ///
+    /// Files rendered from templates are validated first; files of the project edited in
+    /// place are not, since their content is the user's. All are written together; see
+    /// `write_template_files`.
+    ///
+    /// ### Parameters
+    /// - `files` - Files to write
+    pub fn write(&mut self, files: Vec<TemplateFile>) -> Result<()> {
+        let rendered = files
+            .iter()
+            .filter(|file| file.template.is_some())
+            .cloned()
+            .collect::<Vec<_>>();
+        validate_template_files(&rendered)?;
+
+        let mut changes = Vec::new();
+        for file in &files {
+            let path = self.path(&file.file_path);
+            let previous = if path.exists() {
+                Some(self.read(&file.file_path)?)
+            } else {
+                None
+            };
+            self.manifest
+                .record(&file.file_path, previous.as_deref(), &file.content);
+            changes.push((
+                if previous.is_some() {
+                    "update"
+                } else {
+                    "create"
+                },
+                file.file_path.clone(),
+            ));
+        }
+
+        let mut files = files;
+        files.push(self.manifest.to_template_file()?);
+        write_template_files(&self.config, &files)?;
+
+        for (action, file_path) in changes {
+            println!("{}: {}", action, file_path);
+        }
+        Ok(())
+    }
+}
+
+/// Name of a component added to the project, e.g. a stack or a construct.
+///
+/// A trailing `suffix` word is dropped, so `ApiStack` and `api` both name the `Api` stack.
+///
+/// ### Parameters
+/// - `raw` - Name as given on the command line
+/// - `suffix` - Word appended to the class name, e.g. `stack`
+///
+/// ### Returns
+/// - `Naming` - Naming of the component without the suffix
+pub fn component_name(raw: &str, suffix: &str) -> Result<Naming> {
+    let naming = Naming::new(raw);
+    let naming = match naming.kebab.strip_suffix(&format!("-{}", suffix)) {
+        Some(stripped) => Naming::new(stripped),
+        None => naming,
+    };
+    let is_identifier = naming
+        .pascal
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic())
+        && naming.pascal.chars().all(|c| c.is_ascii_alphanumeric());
+    if !is_identifier {
+        anyhow::bail!(
+            "Invalid name '{}': use ASCII letters and digits, starting with a letter",
+            raw
+        );
+    }
+    Ok(naming)
+}
@@ -0,0 +1,234 @@
+use crate::cli::add::stage::ENVIRONMENTS_FILE;
+use crate::cli::add::{component_name, ClassRef, Project};
+use crate::cli::init::{PackageManager, TestTool};
+use crate::templates::render::TemplateContext;
+
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+
+const PIPELINE_TEMPLATE: &str = "add/pipeline.ts";
+const PIPELINE_TEST_TEMPLATE: &str = "add/pipeline.test.ts";
+const PIPELINE_BIN_TEMPLATE: &str = "add/pipeline.bin.ts";
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PipelineSource {
+    /// GitHub repository through a CodeStar connection
+    #[value(name = "github")]
+    GitHub,
+    /// CodeCommit repository
+    #[value(name = "codecommit")]
+    CodeCommit,
+}
+
+#[derive(Debug, Args)]
+pub struct PipelineArgs {
+    /// Name of the pipeline stack
+    #[arg(default_value = "pipeline")]
+    pub name: String,
+
+    /// Where the pipeline gets the source from
+    #[arg(long, value_enum, default_value = "github")]
+    pub source: PipelineSource,
+
+    /// Repository, `owner/repo` on GitHub or the name of the CodeCommit repository
+    #[arg(long)]
+    pub repository: String,
+
+    /// Branch the pipeline deploys
+    #[arg(long, default_value = "main")]
+    pub branch: String,
+
+    /// ARN of the CodeStar connection to GitHub, required with `--source github`
+    #[arg(long, value_name = "ARN")]
+    pub connection_arn: Option<String>,
+
+    /// Rewrite bin/<project>.ts even when it was changed since ts-cdk wrote it
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Variables of the pipeline templates.
+#[derive(Debug, Serialize)]
+struct PipelineContext {
+    #[serde(flatten)]
+    project: TemplateContext,
+    /// Stage deployed to each environment
+    stage: ClassRef,
+    source: PipelineSource,
+    repository: String,
+    branch: String,
+    connection_arn: Option<String>,
+    /// Commands of the synth step preparing the build image
+    install_commands: Vec<String>,
+    /// Commands of the synth step installing dependencies and synthesizing the app
+    commands: Vec<String>,
+}
+
+/// Add a CDK Pipelines stack: `lib/<name>-stack.ts` deploying the stage added by
+/// `add stage` to each environment of `config/environments.ts`, a test unless the project
+/// has no test tool, and a `bin/<project>.ts` instantiating the pipeline stack.
+///
+/// ### Parameters
+/// - `project` - Project to add the pipeline to
+/// - `args` - Arguments of the `add pipeline` command
+pub fn add_pipeline(project: &mut Project, args: &PipelineArgs) -> Result<()> {
+    let name = component_name(&args.name, "stack")?;
+    let stage = match project.find_classes("-stage.ts", "Stage")?.as_slice() {
+        [] => anyhow::bail!("The project has no stage; run `ts-cdk add stage` first"),
+        [stage] => stage.clone(),
+        _ => anyhow::bail!("The project has several stages in lib/; the pipeline deploys one"),
+    };
+    if !project.path(ENVIRONMENTS_FILE).is_file() {
+        anyhow::bail!(
+            "{} does not exist; run `ts-cdk add stage` first",
+            ENVIRONMENTS_FILE
+        );
+    }
+    if args.source == PipelineSource::GitHub
+        && args
+            .repository
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .count()
+            != 2
+    {
+        anyhow::bail!(
+            "Invalid GitHub repository '{}': use `owner/repo`",
+            args.repository
+        );
+    }
+
+    if args.source == PipelineSource::GitHub && args.connection_arn.is_none() {
+        anyhow::bail!("--connection-arn is required with --source github");
+    }
+    // NOTE: the values are written into TypeScript string literals as they are.
+    for value in [
+        Some(&args.repository),
+        Some(&args.branch),
+        args.connection_arn.as_ref(),
+    ]
+    .into_iter()
+    .flatten()
+    {
+        if value.contains(['\'', '\\', '\n']) {
+            anyhow::bail!(
+                "Invalid value '{}': quotes and backslashes are not allowed",
+                value
+            );
+        }
+    }
+
+    let bin_file_path = project.bin_file_path();
+    project.confirm_replace(
+        &bin_file_path,
+        "Replace it with the pipeline stack?",
+        args.force,
+    )?;
+
+    let (install_commands, commands) = synth_commands(project.config.package_manager);
+    let context = PipelineContext {
+        project: project.template_context(&name),
+        stage,
+        source: args.source,
+        repository: args.repository.clone(),
+        branch: args.branch.clone(),
+        connection_arn: args.connection_arn.clone(),
+        install_commands,
+        commands,
+    };
+
+    let mut files = vec![project.new_file(
+        PIPELINE_TEMPLATE,
+        format!("lib/{}-stack.ts", name.kebab),
+        &context,
+    )?];
+    if project.config.test_tool != TestTool::None {
+        files.push(project.new_file(
+            PIPELINE_TEST_TEMPLATE,
+            format!("test/{}-stack.test.ts", name.kebab),
+            &context,
+        )?);
+    }
+    files.push(project.render_file(PIPELINE_BIN_TEMPLATE, bin_file_path, &context)?);
+
+    project.write(files)?;
+    println!(
+        "Commit and push the project, then deploy the pipeline once with `{} cdk deploy {}Stack`.",
+        project.config.package_manager.exec_command(),
+        name.pascal
+    );
+    Ok(())
+}
+
+/// Commands of the synth step, installing dependencies like `install_dependencies` does
+/// with a frozen lockfile.
+///
+/// ### Parameters
+/// - `package_manager` - Package manager of the project
+///
+/// ### Returns
+/// - `(Vec<String>, Vec<String>)` - Install commands and commands of the step
+fn synth_commands(package_manager: PackageManager) -> (Vec<String>, Vec<String>) {
+    // NOTE: the CodeBuild image only ships npm; corepack provides yarn and pnpm.
+    let install_commands = match package_manager {
+        PackageManager::Npm => vec![],
+        PackageManager::Yarn | PackageManager::Pnpm => vec!["corepack enable".to_string()],
+    };
+    let commands = vec![
+        format!(
+            "{} {}",
+            package_manager.command(),
+            package_manager.install_args(true).join(" ")
+        ),
+        format!("{} cdk synth", package_manager.exec_command()),
+    ];
+    (install_commands, commands)
+}
+
+/// Templates of `add pipeline` with their variables for a sample pipeline of each source,
+/// for `template check`.
+///
+/// ### Parameters
+/// - `context` - TemplateContext of the sample component
+///
+/// ### Returns
+/// - `Vec<(&str, serde_json::Value)>` - Template paths with their variables
+pub fn sample_contexts(
+    context: &TemplateContext,
+) -> Result<Vec<(&'static str, serde_json::Value)>> {
+    let (install_commands, commands) = synth_commands(context.package_manager);
+    let mut samples = Vec::new();
+    for (source, repository, connection_arn) in [
+        (
+            PipelineSource::GitHub,
+            "owner/repo",
+            Some("arn:aws:codeconnections:us-east-1:111111111111:connection/sample"),
+        ),
+        (PipelineSource::CodeCommit, "repo", None),
+    ] {
+        let context = serde_json::to_value(PipelineContext {
+            project: context.clone(),
+            stage: ClassRef {
+                class_name: "AppStage".to_string(),
+                file_path: "lib/app-stage.ts".to_string(),
+                module: "app-stage".to_string(),
+            },
+            source,
+            repository: repository.to_string(),
+            branch: "main".to_string(),
+            connection_arn: connection_arn.map(str::to_string),
+            install_commands: install_commands.clone(),
+            commands: commands.clone(),
+        })?;
+        for template_path in [
+            PIPELINE_TEMPLATE,
+            PIPELINE_TEST_TEMPLATE,
+            PIPELINE_BIN_TEMPLATE,
+        ] {
+            samples.push((template_path, context.clone()));
+        }
+    }
+    Ok(samples)
+}
@@ -0,0 +1,264 @@
+use regex::Regex;
+
+/// Insert an import after the last import statement, or at the top of the file after
+/// a shebang line.
+///
+/// ### Parameters
+/// - `source` - TypeScript source
+/// - `import` - Import statement, e.g. `import { ApiStack } from '../lib/api-stack';`
+///
+/// ### Returns
+/// - `String` - Source with the import
+pub fn insert_import(source: &str, import: &str) -> String {
+    let lines = source.lines().collect::<Vec<_>>();
+    let mut insert_at = usize::from(lines.first().is_some_and(|line| line.starts_with("#!")));
+    let mut in_import = false;
+    for (index, line) in lines.iter().enumerate() {
+        let line = line.trim();
+        if line.starts_with("import ") {
+            in_import = true;
+        }
+        // NOTE: an import may span several lines (`import {\n  a,\n} from 'x';`).
+        if in_import && (line.ends_with(';') || line.contains(" from ")) {
+            in_import = false;
+            insert_at = index + 1;
+        }
+    }
+    insert_lines(source, insert_at, &[import])
+}
+
+/// Insert a statement at the end of the file, or before the `<app>.synth()` call when
+/// there is one.
+///
+/// ### Parameters
+/// - `source` - TypeScript source
+/// - `app` - Name of the `cdk.App` variable
+/// - `statement` - Statement to insert
+///
+/// ### Returns
+/// - `String` - Source with the statement
+pub fn insert_statement(source: &str, app: &str, statement: &str) -> String {
+    let synth = format!("{}.synth(", app);
+    let lines = source.lines().collect::<Vec<_>>();
+    let insert_at = lines
+        .iter()
+        .position(|line| line.trim_start().starts_with(&synth))
+        .unwrap_or(lines.len());
+    insert_lines(source, insert_at, &[statement])
+}
+
+/// Make sure a module is imported as a namespace, e.g. `import * as lambda from 'aws-cdk-lib/aws-lambda';`.
+///
+/// ### Parameters
+/// - `source` - TypeScript source
+/// - `module` - Module to import
+/// - `alias` - Namespace used when the module is not imported yet
+///
+/// ### Returns
+/// - `(String, String)` - Source with the import, and the namespace the module is imported as
+pub fn ensure_namespace_import(source: &str, module: &str, alias: &str) -> (String, String) {
+    let pattern = Regex::new(&format!(
+        r#"import\s+\*\s+as\s+([A-Za-z_$][\w$]*)\s+from\s+['"]{}['"]"#,
+        regex::escape(module)
+    ))
+    .expect("import pattern is valid");
+    match pattern.captures(source) {
+        Some(captures) => (source.to_string(), captures[1].to_string()),
+        None => (
+            insert_import(source, &format!("import * as {} from '{}';", alias, module)),
+            alias.to_string(),
+        ),
+    }
+}
+
+/// Insert lines at the end of the body of the first constructor, indented like its body.
+///
+/// ### Parameters
+/// - `source` - TypeScript source
+/// - `new_lines` - Lines to insert, indented relative to the constructor body
+///
+/// ### Returns
+/// - `Option<String>` - Source with the lines, `None` when no constructor body ending on
+///   its own line was found
+pub fn insert_in_constructor(source: &str, new_lines: &[&str]) -> Option<String> {
+    let chars = source.chars().collect::<Vec<char>>();
+    let code = code_mask(&chars);
+
+    let keyword = "constructor(".chars().collect::<Vec<char>>();
+    let start = (0..chars.len()).find(|&index| {
+        chars[index..].starts_with(&keyword)
+            && code[index..index + keyword.len()].iter().all(|&c| c)
+    })?;
+    let params_end = matching_delimiter(&chars, &code, start + keyword.len() - 1)?;
+    let body_start =
+        (params_end + 1..chars.len()).find(|&index| code[index] && chars[index] == '{')?;
+    let body_end = matching_delimiter(&chars, &code, body_start)?;
+
+    let line_index = chars[..body_end].iter().filter(|&&c| c == '\n').count();
+    let closing_line = source.lines().nth(line_index)?;
+    if closing_line.trim() != "}" {
+        return None;
+    }
+    let indent = format!(
+        "{}  ",
+        &closing_line[..closing_line.len() - closing_line.trim_start().len()]
+    );
+
+    let indented = std::iter::once(String::new())
+        .chain(new_lines.iter().map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", indent, line)
+            }
+        }))
+        .collect::<Vec<_>>();
+    let indented = indented.iter().map(String::as_str).collect::<Vec<_>>();
+    Some(insert_lines(source, line_index, &indented))
+}
+
+/// Name of the variable holding the `cdk.App`, e.g. `app` in `const app = new cdk.App();`.
+pub fn find_app_variable(source: &str) -> Option<String> {
+    let pattern =
+        Regex::new(r"(?m)^\s*(?:const|let)\s+([A-Za-z_$][\w$]*)\s*=\s*new\s+(?:cdk\.)?App\(")
+            .expect("app pattern is valid");
+    pattern
+        .captures(source)
+        .map(|captures| captures[1].to_string())
+}
+
+/// Whether an identifier is used anywhere in the source.
+pub fn contains_identifier(source: &str, identifier: &str) -> bool {
+    Regex::new(&format!(r"\b{}\b", regex::escape(identifier)))
+        .map(|pattern| pattern.is_match(source))
+        .unwrap_or(false)
+}
+
+/// Which characters are code, as opposed to comments, strings and template literals.
+fn code_mask(chars: &[char]) -> Vec<bool> {
+    let mut code = vec![true; chars.len()];
+    let mut index = 0;
+    while index < chars.len() {
+        let end = match (chars[index], chars.get(index + 1)) {
+            ('/', Some('/')) => (index..chars.len())
+                .find(|&end| chars[end] == '\n')
+                .unwrap_or(chars.len()),
+            ('/', Some('*')) => (index + 2..chars.len())
+                .find(|&end| chars[end - 1] == '*' && chars[end] == '/' && end > index + 2)
+                .map_or(chars.len(), |end| end + 1),
+            (quote @ ('\'' | '"' | '`'), _) => {
+                let mut end = index + 1;
+                while end < chars.len() && chars[end] != quote {
+                    if chars[end] == '\\' {
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                (end + 1).min(chars.len())
+            }
+            _ => {
+                index += 1;
+                continue;
+            }
+        };
+        code[index..end].iter_mut().for_each(|c| *c = false);
+        index = end;
+    }
+    code
+}
+
+/// Index of the delimiter closing the one at `open`, ignoring anything that is not code.
+fn matching_delimiter(chars: &[char], code: &[bool], open: usize) -> Option<usize> {
+    let (opener, closer) = match chars[open] {
+        '(' => ('(', ')'),
+        '{' => ('{', '}'),
+        '[' => ('[', ']'),
+        _ => return None,
+    };
+    let mut depth = 0;
+    for index in open..chars.len() {
+        if !code[index] {
+            continue;
+        }
+        if chars[index] == opener {
+            depth += 1;
+        } else if chars[index] == closer {
+            depth -= 1;
+            if depth == 0 {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+/// Insert lines before the line at `index`, keeping the line endings of the source.
+fn insert_lines(source: &str, index: usize, new_lines: &[&str]) -> String {
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut lines = source.lines().collect::<Vec<_>>();
+    let index = index.min(lines.len());
+    lines.splice(index..index, new_lines.iter().copied());
+    let mut result = lines.join(newline);
+    result.push_str(newline);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inserts_import_after_the_last_import() {
+        let source =
+            "#!/usr/bin/env node\nimport {\n  A,\n} from './a';\n\nconst app = new cdk.App();\n";
+        assert_eq!(
+            insert_import(source, "import { B } from './b';"),
+            "#!/usr/bin/env node\nimport {\n  A,\n} from './a';\nimport { B } from './b';\n\nconst app = new cdk.App();\n"
+        );
+        assert_eq!(
+            insert_import("#!/usr/bin/env node\nfoo();", "import './b';"),
+            "#!/usr/bin/env node\nimport './b';\nfoo();\n"
+        );
+    }
+
+    #[test]
+    fn inserts_into_the_constructor_body() {
+        let source = "export class A extends cdk.Stack {\n  constructor(scope: Construct, id: string, props: AProps = {}) {\n    super(scope, id);\n    // new B(this, 'B', {\n    const c = '}';\n  }\n}\n";
+        assert_eq!(
+            insert_in_constructor(source, &["new D(this, 'D', {", "  e: 1,", "});"]).unwrap(),
+            "export class A extends cdk.Stack {\n  constructor(scope: Construct, id: string, props: AProps = {}) {\n    super(scope, id);\n    // new B(this, 'B', {\n    const c = '}';\n\n    new D(this, 'D', {\n      e: 1,\n    });\n  }\n}\n"
+        );
+        assert_eq!(insert_in_constructor("const a = 1;\n", &["b();"]), None);
+    }
+
+    #[test]
+    fn reuses_existing_namespace_imports() {
+        let source = "import * as awsLambda from 'aws-cdk-lib/aws-lambda';\n";
+        assert_eq!(
+            ensure_namespace_import(source, "aws-cdk-lib/aws-lambda", "lambda"),
+            (source.to_string(), "awsLambda".to_string())
+        );
+        assert_eq!(
+            ensure_namespace_import(source, "path", "path").0,
+            "import * as awsLambda from 'aws-cdk-lib/aws-lambda';\nimport * as path from 'path';\n"
+        );
+    }
+
+    #[test]
+    fn inserts_statement_before_synth() {
+        let source = "const app = new cdk.App();\napp.synth();\n";
+        assert_eq!(find_app_variable(source).as_deref(), Some("app"));
+        assert_eq!(
+            insert_statement(source, "app", "new A(app, 'A');"),
+            "const app = new cdk.App();\nnew A(app, 'A');\napp.synth();\n"
+        );
+        assert_eq!(
+            insert_statement("const app = new App();", "app", "new A(app, 'A');"),
+            "const app = new App();\nnew A(app, 'A');\n"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::cli::add::source_edit::{
+    contains_identifier, find_app_variable, insert_import, insert_in_constructor, insert_statement,
+};
+use crate::cli::add::{component_name, Project};
+use crate::cli::init::TestTool;
+use crate::templates::generate::{generate_template_file, TemplateFile};
+use crate::templates::registry::registry_entry;
+
+use anyhow::Result;
+use clap::Args;
+
+/// Template of the stack generated by `init`, reused for every added stack.
+const STACK_TEMPLATE: &str = "lib/%project-name%-stack.ts";
+const STACK_TEST_TEMPLATE: &str = "add/stack.test.ts";
+
+#[derive(Debug, Args)]
+pub struct StackArgs {
+    /// Name of the stack, e.g. `api` or `ApiStack`
+    pub name: String,
+}
+
+/// Add a stack: `lib/<name>-stack.ts`, a test unless the project has no test tool,
+/// and its instantiation in the stage added by `add stage`, or in `bin/<project>.ts`
+/// when there is none.
+///
+/// ### Parameters
+/// - `project` - Project to add the stack to
+/// - `args` - Arguments of the `add stack` command
+pub fn add_stack(project: &mut Project, args: &StackArgs) -> Result<()> {
+    let name = component_name(&args.name, "stack")?;
+    let class_name = format!("{}Stack", name.pascal);
+    let context = project.template_context(&name);
+
+    let entry = registry_entry(STACK_TEMPLATE)
+        .ok_or_else(|| anyhow::anyhow!("{} is not a registered template", STACK_TEMPLATE))?;
+    let stack = generate_template_file(entry, entry.output, &context, &project.loader)?;
+    if project.path(&stack.file_path).exists() {
+        anyhow::bail!("{} already exists", stack.file_path);
+    }
+
+    let stages = project.find_classes("-stage.ts", "Stage")?;
+    let (file_path, source) = match stages.as_slice() {
+        [] => {
+            let bin_file_path = project.bin_file_path();
+            let bin = project.read(&bin_file_path)?;
+            if contains_identifier(&bin, &class_name) {
+                anyhow::bail!("{} is already used in {}", class_name, bin_file_path);
+            }
+            let app = find_app_variable(&bin).ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Cannot find `new cdk.App()` in {}; add {} by hand",
+                    bin_file_path,
+                    class_name
+                )
+            })?;
+            let bin = insert_import(
+                &bin,
+                &format!(
+                    "import {{ {} }} from '../lib/{}-stack';",
+                    class_name, name.kebab
+                ),
+            );
+            let bin = insert_statement(
+                &bin,
+                &app,
+                &format!("new {}({}, '{}');", class_name, app, class_name),
+            );
+            (bin_file_path, bin)
+        }
+        [stage_class] => {
+            // NOTE: with a stage, bin/ instantiates the stage and the stage its stacks.
+            let stage = project.read(&stage_class.file_path)?;
+            if contains_identifier(&stage, &class_name) {
+                anyhow::bail!(
+                    "{} is already used in {}",
+                    class_name,
+                    stage_class.file_path
+                );
+            }
+            let stage = insert_import(
+                &stage,
+                &format!("import {{ {} }} from './{}-stack';", class_name, name.kebab),
+            );
+            let stage = insert_in_constructor(
+                &stage,
+                &[&format!("new {}(this, '{}');", class_name, class_name)],
+            )
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "Cannot find the constructor of {} in {}; add {} by hand",
+                    stage_class.class_name,
+                    stage_class.file_path,
+                    class_name
+                )
+            })?;
+            (stage_class.file_path.clone(), stage)
+        }
+        _ => anyhow::bail!(
+            "The project has several stages in lib/; add {} to one of them by hand",
+            class_name
+        ),
+    };
+
+    let mut files = vec![stack];
+    if project.config.test_tool != TestTool::None {
+        files.push(project.new_file(
+            STACK_TEST_TEMPLATE,
+            format!("test/{}-stack.test.ts", name.kebab),
+            &context,
+        )?);
+    }
+    files.push(TemplateFile {
+        file_path,
+        content: source,
+        template: None,
+    });
+
+    project.write(files)
+}
@@ -0,0 +1,124 @@
+use crate::cli::add::{component_name, ClassRef, Project};
+use crate::cli::init::TestTool;
+use crate::templates::render::TemplateContext;
+
+use anyhow::Result;
+use clap::Args;
+use serde::Serialize;
+
+const STAGE_TEMPLATE: &str = "add/stage.ts";
+const STAGE_TEST_TEMPLATE: &str = "add/stage.test.ts";
+const STAGE_BIN_TEMPLATE: &str = "add/stage.bin.ts";
+const ENVIRONMENTS_TEMPLATE: &str = "add/environments.ts";
+pub const ENVIRONMENTS_FILE: &str = "config/environments.ts";
+
+#[derive(Debug, Args)]
+pub struct StageArgs {
+    /// Name of the stage
+    #[arg(default_value = "app")]
+    pub name: String,
+
+    /// Rewrite bin/<project>.ts even when it was changed since ts-cdk wrote it
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Variables of the stage templates.
+#[derive(Debug, Serialize)]
+struct StageContext {
+    #[serde(flatten)]
+    project: TemplateContext,
+    /// Stacks grouped by the stage
+    stacks: Vec<ClassRef>,
+}
+
+/// Add a stage: `lib/<name>-stage.ts` grouping every stack of lib/, a test unless the
+/// project has no test tool, `config/environments.ts` unless it exists, and a
+/// `bin/<project>.ts` instantiating the stage once per environment.
+///
+/// ### Parameters
+/// - `project` - Project to add the stage to
+/// - `args` - Arguments of the `add stage` command
+pub fn add_stage(project: &mut Project, args: &StageArgs) -> Result<()> {
+    let name = component_name(&args.name, "stage")?;
+    let stacks = project.find_classes("-stack.ts", "Stack")?;
+    if stacks.is_empty() {
+        anyhow::bail!("No stacks found in lib/");
+    }
+    if let Some(stage) = project.find_classes("-stage.ts", "Stage")?.first() {
+        anyhow::bail!(
+            "The project already has a stage, {} in {}",
+            stage.class_name,
+            stage.file_path
+        );
+    }
+
+    let bin_file_path = project.bin_file_path();
+    project.confirm_replace(
+        &bin_file_path,
+        "Replace it with a loop over the environments?",
+        args.force,
+    )?;
+
+    let context = StageContext {
+        project: project.template_context(&name),
+        stacks,
+    };
+    let mut files = vec![project.new_file(
+        STAGE_TEMPLATE,
+        format!("lib/{}-stage.ts", name.kebab),
+        &context,
+    )?];
+    if project.config.test_tool != TestTool::None {
+        files.push(project.new_file(
+            STAGE_TEST_TEMPLATE,
+            format!("test/{}-stage.test.ts", name.kebab),
+            &context,
+        )?);
+    }
+    // NOTE: environments that were already configured are kept.
+    if !project.path(ENVIRONMENTS_FILE).exists() {
+        files.push(project.new_file(
+            ENVIRONMENTS_TEMPLATE,
+            ENVIRONMENTS_FILE.to_string(),
+            &context,
+        )?);
+    }
+    files.push(project.render_file(STAGE_BIN_TEMPLATE, bin_file_path, &context)?);
+
+    project.write(files)?;
+    println!(
+        "Set the accounts and regions of your environments in {}.",
+        ENVIRONMENTS_FILE
+    );
+    Ok(())
+}
+
+/// Templates of `add stage` with their variables for a sample stage, for `template check`.
+///
+/// ### Parameters
+/// - `context` - TemplateContext of the sample component
+///
+/// ### Returns
+/// - `Vec<(&str, serde_json::Value)>` - Template paths with their variables
+pub fn sample_contexts(
+    context: &TemplateContext,
+) -> Result<Vec<(&'static str, serde_json::Value)>> {
+    let context = serde_json::to_value(StageContext {
+        project: context.clone(),
+        stacks: vec![ClassRef {
+            class_name: format!("{}Stack", context.name.pascal),
+            file_path: format!("lib/{}-stack.ts", context.name.kebab),
+            module: format!("{}-stack", context.name.kebab),
+        }],
+    })?;
+    Ok([
+        STAGE_TEMPLATE,
+        STAGE_TEST_TEMPLATE,
+        STAGE_BIN_TEMPLATE,
+        ENVIRONMENTS_TEMPLATE,
+    ]
+    .into_iter()
+    .map(|template_path| (template_path, context.clone()))
+    .collect())
+}
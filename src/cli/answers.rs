@@ -0,0 +1,141 @@
+use crate::cli::init::ProjectConfig;
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AnswersFormat {
+    Toml,
+    Json,
+    Yaml,
+}
+
+/// Load a ProjectConfig from an answers file.
+///
+/// The format is chosen by the file extension (`.toml`, `.json`, `.yaml` or `.yml`).
+///
+/// ### Parameters
+/// - `path` - Path to the answers file
+///
+/// ### Returns
+/// - `ProjectConfig` - ProjectConfig without a target directory
+pub fn load_answers(path: &Path) -> Result<ProjectConfig> {
+    let format = detect_format(path)?;
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read answers file {}", path.display()))?;
+
+    let config = match format {
+        AnswersFormat::Toml => toml::from_str(&content).map_err(anyhow::Error::from),
+        AnswersFormat::Json => serde_json::from_str(&content).map_err(anyhow::Error::from),
+        AnswersFormat::Yaml => serde_yaml::from_str(&content).map_err(anyhow::Error::from),
+    }
+    .with_context(|| format!("Failed to parse answers file {}", path.display()))?;
+
+    Ok(config)
+}
+
+/// Save a ProjectConfig as an answers file.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `path` - Path to the answers file, its extension decides the format
+pub fn save_answers(config: &ProjectConfig, path: &Path) -> Result<()> {
+    let content = match detect_format(path)? {
+        AnswersFormat::Toml => toml::to_string(config)?,
+        AnswersFormat::Json => serde_json::to_string_pretty(config)? + "\n",
+        AnswersFormat::Yaml => serde_yaml::to_string(config)?,
+    };
+    std::fs::write(path, content)
+        .with_context(|| format!("Failed to write answers file {}", path.display()))?;
+
+    Ok(())
+}
+
+fn detect_format(path: &Path) -> Result<AnswersFormat> {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("toml") => Ok(AnswersFormat::Toml),
+        Some("json") => Ok(AnswersFormat::Json),
+        Some("yaml") | Some("yml") => Ok(AnswersFormat::Yaml),
+        _ => anyhow::bail!(
+            "Unsupported answers file '{}' (expected .toml, .json, .yaml or .yml)",
+            path.display()
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::{Formatter, Linter, PackageManager, TestTool};
+    use crate::templates::template_manifest::VariableValue;
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn config() -> ProjectConfig {
+        ProjectConfig {
+            target_dir_path: PathBuf::from("my-app"),
+            name: "@acme/my-app".to_string(),
+            package_manager: PackageManager::Pnpm,
+            linter: Linter::Biome,
+            formatter: Formatter::None,
+            test_tool: TestTool::Vitest,
+            package_manager_version: Some("9.15.4".to_string()),
+            variables: BTreeMap::from([
+                ("use_vpc".to_string(), VariableValue::Bool(true)),
+                (
+                    "aws_account_id".to_string(),
+                    VariableValue::String("123456789012".to_string()),
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn round_trips_answers() {
+        let dir = tempfile::tempdir().unwrap();
+        for extension in ["toml", "json", "yaml", "yml"] {
+            let path = dir.path().join(format!("answers.{}", extension));
+            save_answers(&config(), &path).unwrap();
+            let loaded = load_answers(&path).unwrap();
+            let expected = config();
+            // NOTE: the target directory is never saved.
+            assert_eq!(loaded.target_dir_path, PathBuf::new(), "{}", extension);
+            assert_eq!(loaded.name, expected.name, "{}", extension);
+            assert_eq!(loaded.package_manager, expected.package_manager);
+            assert_eq!(loaded.linter, expected.linter);
+            assert_eq!(loaded.formatter, expected.formatter);
+            assert_eq!(loaded.test_tool, expected.test_tool);
+            assert_eq!(
+                loaded.package_manager_version,
+                expected.package_manager_version
+            );
+            assert_eq!(loaded.variables, expected.variables, "{}", extension);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let answers = [
+            (
+                "toml",
+                "name = \"my-app\"\npackage_manager = \"npm\"\nlinter = \"eslint\"\nformatter = \"prettier\"\ntest_tool = \"jest\"\ntest_tools = \"vitest\"\n",
+            ),
+            (
+                "json",
+                "{\"name\": \"my-app\", \"package_manager\": \"npm\", \"linter\": \"eslint\", \"formatter\": \"prettier\", \"test_tool\": \"jest\", \"target_dir_path\": \"..\"}",
+            ),
+            (
+                "yaml",
+                "name: my-app\npackage_manager: npm\nlinter: eslint\nformatter: prettier\ntest_tool: jest\npreset: team\n",
+            ),
+        ];
+        for (extension, content) in answers {
+            let path = dir.path().join(format!("answers.{}", extension));
+            std::fs::write(&path, content).unwrap();
+            let error = format!("{:#}", load_answers(&path).unwrap_err());
+            assert!(error.contains("unknown field"), "{}: {}", extension, error);
+        }
+        assert!(load_answers(&dir.path().join("answers.ini")).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+use crate::cli::init::ProjectConfig;
+use crate::templates::generate::TemplateFile;
+
+use anyhow::Result;
+use dialoguer::Select;
+use similar::TextDiff;
+
+/// How to handle generated files that already exist in the target directory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConflictStrategy {
+    /// Ask for every conflicting file
+    Ask,
+    /// Overwrite every conflicting file (`--force`)
+    Overwrite,
+    /// Keep every existing file (`--skip-existing`)
+    Skip,
+}
+
+/// Check generated files against the target directory and decide which ones to write.
+///
+/// Existing files with identical content are left untouched.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `files` - Files returned by `generate_template_files`
+/// - `strategy` - How to handle conflicting files
+/// - `interactive` - Whether prompts may be shown
+///
+/// ### Returns
+/// - `Vec<TemplateFile>` - Files that should be written
+pub fn resolve_conflicts(
+    config: &ProjectConfig,
+    files: Vec<TemplateFile>,
+    strategy: ConflictStrategy,
+    interactive: bool,
+) -> Result<Vec<TemplateFile>> {
+    let target_dir = config.target_dir_path.as_path();
+    if target_dir.exists() && !target_dir.is_dir() {
+        anyhow::bail!("'{}' exists and is not a directory", target_dir.display());
+    }
+
+    let mut files_to_write = Vec::new();
+    let mut conflicts = Vec::new();
+    for file in files {
+        let path = target_dir.join(&file.file_path);
+        if path.is_dir() {
+            anyhow::bail!("'{}' exists and is a directory", path.display());
+        }
+        match std::fs::read(&path) {
+            Ok(existing) if existing == file.content.as_bytes() => continue,
+            Ok(existing) => conflicts.push((file, String::from_utf8_lossy(&existing).into_owned())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => files_to_write.push(file),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    if conflicts.is_empty() {
+        return Ok(files_to_write);
+    }
+
+    match strategy {
+        ConflictStrategy::Overwrite => {
+            for (file, _) in conflicts {
+                println!("overwrite: {}", file.file_path);
+                files_to_write.push(file);
+            }
+        }
+        ConflictStrategy::Skip => {
+            for (file, _) in conflicts {
+                println!("skip: {}", file.file_path);
+            }
+        }
+        ConflictStrategy::Ask if !interactive => {
+            let paths = conflicts
+                .iter()
+                .map(|(file, _)| format!("  {}", file.file_path))
+                .collect::<Vec<_>>()
+                .join("\n");
+            anyhow::bail!(
+                "The following files already exist in '{}':\n{}\nUse --force to overwrite them or --skip-existing to keep them",
+                target_dir.display(),
+                paths
+            );
+        }
+        ConflictStrategy::Ask => {
+            for (file, existing) in conflicts {
+                if ask_overwrite(&file, &existing)? {
+                    files_to_write.push(file);
+                }
+            }
+        }
+    }
+
+    Ok(files_to_write)
+}
+
+/// Ask whether to overwrite an existing file, showing a diff on request.
+fn ask_overwrite(file: &TemplateFile, existing: &str) -> Result<bool> {
+    let choices = &["overwrite", "skip", "show diff", "abort"];
+    loop {
+        let choice = Select::new()
+            .with_prompt(format!("'{}' already exists", file.file_path))
+            .items(choices)
+            .default(1)
+            .interact()?;
+        match choices[choice] {
+            "overwrite" => return Ok(true),
+            "skip" => return Ok(false),
+            "show diff" => {
+                let diff = TextDiff::from_lines(existing, file.content.as_str());
+                let existing_header = format!("{} (existing)", file.file_path);
+                let generated_header = format!("{} (generated)", file.file_path);
+                print!(
+                    "{}",
+                    diff.unified_diff()
+                        .header(&existing_header, &generated_header)
+                        .missing_newline_hint(false)
+                );
+            }
+            "abort" => anyhow::bail!("Aborted: '{}' already exists", file.file_path),
+            _ => unreachable!(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::{Formatter, Linter, PackageManager, TestTool};
+    use std::path::{Path, PathBuf};
+
+    fn config(target_dir_path: PathBuf) -> ProjectConfig {
+        ProjectConfig {
+            target_dir_path,
+            name: "my-app".to_string(),
+            package_manager: PackageManager::Npm,
+            linter: Linter::EsLint,
+            formatter: Formatter::Prettier,
+            test_tool: TestTool::Jest,
+            package_manager_version: None,
+            variables: Default::default(),
+        }
+    }
+
+    fn file(file_path: &str, content: &str) -> TemplateFile {
+        TemplateFile {
+            file_path: file_path.to_string(),
+            content: content.to_string(),
+            template: None,
+        }
+    }
+
+    /// A target directory holding `same.txt`, identical to the generated file, and
+    /// `changed.txt`, which differs from it; `new.txt` does not exist yet.
+    fn target_dir() -> (tempfile::TempDir, Vec<TemplateFile>) {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.txt"), "same\n").unwrap();
+        std::fs::write(dir.path().join("changed.txt"), "edited\n").unwrap();
+        let files = vec![
+            file("same.txt", "same\n"),
+            file("changed.txt", "generated\n"),
+            file("new.txt", "new\n"),
+        ];
+        (dir, files)
+    }
+
+    fn paths(files: &[TemplateFile]) -> Vec<&str> {
+        files.iter().map(|file| file.file_path.as_str()).collect()
+    }
+
+    fn resolve(
+        dir: &Path,
+        files: Vec<TemplateFile>,
+        strategy: ConflictStrategy,
+    ) -> Result<Vec<TemplateFile>> {
+        resolve_conflicts(&config(dir.to_path_buf()), files, strategy, false)
+    }
+
+    #[test]
+    fn writes_new_and_skips_identical_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("same.txt"), "same\n").unwrap();
+        let files = vec![file("same.txt", "same\n"), file("new.txt", "new\n")];
+        // NOTE: without conflicts, no strategy matters, not even asking without a terminal.
+        let files = resolve(dir.path(), files, ConflictStrategy::Ask).unwrap();
+        assert_eq!(paths(&files), vec!["new.txt"]);
+    }
+
+    #[test]
+    fn overwrites_or_keeps_conflicting_files() {
+        let (dir, files) = target_dir();
+        let files = resolve(dir.path(), files, ConflictStrategy::Overwrite).unwrap();
+        assert_eq!(paths(&files), vec!["new.txt", "changed.txt"]);
+        assert_eq!(files[1].content, "generated\n");
+
+        let (dir, files) = target_dir();
+        let files = resolve(dir.path(), files, ConflictStrategy::Skip).unwrap();
+        assert_eq!(paths(&files), vec!["new.txt"]);
+    }
+
+    #[test]
+    fn fails_on_conflicts_without_a_terminal() {
+        let (dir, files) = target_dir();
+        let error = resolve(dir.path(), files, ConflictStrategy::Ask)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("\n  changed.txt\n"), "{}", error);
+        assert!(!error.contains("same.txt"), "{}", error);
+        assert!(error.contains("--force"), "{}", error);
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("new.txt")).unwrap();
+        let error = resolve(
+            dir.path(),
+            vec![file("new.txt", "new\n")],
+            ConflictStrategy::Overwrite,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("is a directory"), "{}", error);
+    }
+}
@@ -0,0 +1,95 @@
+use crate::cli::hooks::{print_hooks, Hook};
+use crate::cli::init::ProjectConfig;
+use crate::templates::generate::TemplateFile;
+
+use std::collections::BTreeMap;
+
+#[derive(Debug, Default)]
+struct TreeNode<'a> {
+    directories: BTreeMap<&'a str, TreeNode<'a>>,
+    files: BTreeMap<&'a str, usize>,
+}
+
+/// Print the files that `init` would write, without touching the disk.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `files` - Files returned by `generate_template_files`
+/// - `hooks` - Hooks of the template pack
+/// - `show_contents` - Also print the full content of every file
+pub fn print_plan(
+    config: &ProjectConfig,
+    files: &[TemplateFile],
+    hooks: &[Hook],
+    show_contents: bool,
+) {
+    let mut root = TreeNode::default();
+    for file in files {
+        let mut node = &mut root;
+        let mut segments = file.file_path.split('/').peekable();
+        while let Some(segment) = segments.next() {
+            if segments.peek().is_some() {
+                node = node.directories.entry(segment).or_default();
+            } else {
+                node.files.insert(segment, file.content.len());
+            }
+        }
+    }
+
+    let total_size = files.iter().map(|file| file.content.len()).sum();
+    println!(
+        "{} ({} files, {})",
+        config.target_dir_path.display(),
+        files.len(),
+        format_size(total_size)
+    );
+    print_tree(&root, "");
+
+    if show_contents {
+        for file in files {
+            println!();
+            println!("==> {} <==", file.file_path);
+            println!("{}", file.content.trim_end());
+        }
+    }
+
+    if !hooks.is_empty() {
+        println!();
+        println!("Hooks:");
+        print_hooks(hooks);
+    }
+
+    println!();
+    println!("Dry run: no files were written and no dependencies were installed.");
+}
+
+fn print_tree(node: &TreeNode, prefix: &str) {
+    // NOTE: directories first, then files, each in alphabetical order.
+    let entries: Vec<(&str, Option<&TreeNode>, usize)> = node
+        .directories
+        .iter()
+        .map(|(name, child)| (*name, Some(child), 0))
+        .chain(node.files.iter().map(|(name, size)| (*name, None, *size)))
+        .collect();
+
+    for (index, (name, child, size)) in entries.iter().enumerate() {
+        let is_last = index == entries.len() - 1;
+        let connector = if is_last { "└── " } else { "├── " };
+        match child {
+            Some(child) => {
+                println!("{}{}{}", prefix, connector, name);
+                let child_prefix = format!("{}{}", prefix, if is_last { "    " } else { "│   " });
+                print_tree(child, &child_prefix);
+            }
+            None => println!("{}{}{} ({})", prefix, connector, name, format_size(*size)),
+        }
+    }
+}
+
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
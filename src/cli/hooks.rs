@@ -0,0 +1,121 @@
+use crate::cli::init::ProjectConfig;
+use crate::cli::process::run_logged;
+use crate::templates::render::{evaluate_condition, render_template, TemplateContext};
+use crate::templates::template_manifest::TemplateManifest;
+
+use anyhow::{Context, Result};
+use dialoguer::Confirm;
+use std::process::Command;
+
+/// A hook of the template manifest, ready to run.
+#[derive(Debug, Clone)]
+pub struct Hook {
+    pub name: String,
+    /// Shell command
+    pub command: String,
+}
+
+/// Select the hooks whose condition holds and render their commands.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `manifest` - Manifest of the template pack
+///
+/// ### Returns
+/// - `Vec<Hook>` - Hooks to run, in declaration order
+pub fn plan_hooks(config: &ProjectConfig, manifest: &TemplateManifest) -> Result<Vec<Hook>> {
+    let context = TemplateContext::new(config);
+    let mut hooks = Vec::new();
+    for hook in &manifest.hooks {
+        if let Some(expression) = &hook.when {
+            let applies = evaluate_condition(expression, &context)
+                .with_context(|| format!("Invalid condition of hook `{}`", hook.run))?;
+            if !applies {
+                continue;
+            }
+        }
+        let command = render_template("hook", &hook.run, &context)?;
+        hooks.push(Hook {
+            name: hook.name.clone().unwrap_or_else(|| command.clone()),
+            command,
+        });
+    }
+    Ok(hooks)
+}
+
+/// Print the hooks that would run.
+pub fn print_hooks(hooks: &[Hook]) {
+    for hook in hooks {
+        if hook.name == hook.command {
+            println!("  - `{}`", hook.command);
+        } else {
+            println!("  - {}: `{}`", hook.name, hook.command);
+        }
+    }
+}
+
+/// Run hooks in the project directory, one after another, stopping at the first failure.
+///
+/// The hooks are shown and must be confirmed first, unless `allowed` (`--yes`) is set.
+/// Without `allowed` and without prompts, they are not run.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `hooks` - Hooks returned by `plan_hooks`
+/// - `allowed` - Run without asking
+/// - `interactive` - Whether prompts may be shown
+pub fn run_hooks(
+    config: &ProjectConfig,
+    hooks: &[Hook],
+    allowed: bool,
+    interactive: bool,
+) -> Result<()> {
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    println!(
+        "The template runs these commands in {}:",
+        config.target_dir_path.display()
+    );
+    print_hooks(hooks);
+    if !allowed {
+        let confirmed = interactive
+            && Confirm::new()
+                .with_prompt("Run them?")
+                .default(false)
+                .interact()?;
+        if !confirmed {
+            if interactive {
+                println!("Skipped the template hooks.");
+            } else {
+                println!("Skipped the template hooks (use --yes to run them without asking).");
+            }
+            return Ok(());
+        }
+    }
+
+    for hook in hooks {
+        println!("Running {}...", hook.name);
+        run_logged(
+            shell_command(&hook.command).current_dir(&config.target_dir_path),
+            &hook.command,
+            "ts-cdk-hook-",
+        )
+        .with_context(|| format!("Hook '{}' failed", hook.name))?;
+    }
+    Ok(())
+}
+
+/// Command running `command` with the platform shell.
+fn shell_command(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut shell = Command::new("cmd");
+        shell.args(["/C", command]);
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.args(["-c", command]);
+        shell
+    }
+}
@@ -1,163 +1,750 @@
-use anyhow::Result;
-use dialoguer::{Input, Select};
+use crate::cli::answers;
+use crate::cli::conflict::ConflictStrategy;
+use crate::cli::process::run_logged;
+use crate::cli::project_name::validate_project_name;
+use crate::cli::user_config::{Preset, UserConfig};
+use crate::templates::generate::TemplateFile;
+use crate::templates::git::GitTemplate;
+use crate::templates::loader::TemplateLoader;
+use crate::templates::manifest::TemplateSources;
+use crate::templates::naming::Naming;
+use crate::templates::template_manifest::{
+    PromptDefinition, PromptKind, TemplateManifest, VariableValue,
+};
+use crate::templates::write::WrittenProject;
 
-#[derive(Debug)]
+use anyhow::{Context, Result};
+use clap::{Args, ValueEnum};
+use dialoguer::{Confirm, Input, Select};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::IsTerminal;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ProjectConfig {
-    pub target_dir_path: String,
+    // NOTE: the target directory always comes from the command line, never from an answers file.
+    #[serde(skip)]
+    pub target_dir_path: PathBuf,
     pub name: String,
     pub package_manager: PackageManager,
     pub linter: Linter,
     pub formatter: Formatter,
     pub test_tool: TestTool,
+    /// Version `packageManager` of package.json pins, given by `--pin-package-manager`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager_version: Option<String>,
+    /// Answers to the prompts of the template pack's `template.toml`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub variables: BTreeMap<String, VariableValue>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PackageManager {
     Npm,
     Yarn,
     Pnpm,
 }
 
-#[derive(Debug, PartialEq)]
+impl PackageManager {
+    /// Name of the package manager executable.
+    pub fn command(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npm",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm",
+        }
+    }
+
+    /// Arguments of the install command.
+    ///
+    /// ### Parameters
+    /// - `frozen_lockfile` - Fail instead of updating the lockfile (CI mode)
+    pub fn install_args(&self, frozen_lockfile: bool) -> &'static [&'static str] {
+        match (self, frozen_lockfile) {
+            (PackageManager::Npm, false) => &["install"],
+            (PackageManager::Npm, true) => &["ci"],
+            (PackageManager::Yarn, false) => &["install"],
+            (PackageManager::Yarn, true) => &["install", "--immutable"],
+            (PackageManager::Pnpm, false) => &["install"],
+            (PackageManager::Pnpm, true) => &["install", "--frozen-lockfile"],
+        }
+    }
+
+    /// Lockfiles the frozen-lockfile install reads; any one of them is enough.
+    pub fn lockfiles(&self) -> &'static [&'static str] {
+        match self {
+            PackageManager::Npm => &["package-lock.json", "npm-shrinkwrap.json"],
+            PackageManager::Yarn => &["yarn.lock"],
+            PackageManager::Pnpm => &["pnpm-lock.yaml"],
+        }
+    }
+
+    /// Command running a binary of an installed package, e.g. `npx` in `npx cdk synth`.
+    pub fn exec_command(&self) -> &'static str {
+        match self {
+            PackageManager::Npm => "npx",
+            PackageManager::Yarn => "yarn",
+            PackageManager::Pnpm => "pnpm exec",
+        }
+    }
+
+    /// Version of the package manager on PATH.
+    ///
+    /// ### Returns
+    /// - `String` - Version, e.g. `9.15.4`
+    pub fn installed_version(&self) -> Result<String> {
+        let program = which::which(self.command())
+            .with_context(|| format!("{} is not installed", self.command()))?;
+        let output = Command::new(program)
+            .arg("--version")
+            .output()
+            .with_context(|| format!("Failed to run {} --version", self.command()))?;
+        let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        let is_version = version.split('.').count() == 3
+            && version
+                .split('.')
+                .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+        if !output.status.success() || !is_version {
+            anyhow::bail!(
+                "Failed to read the version of {} ('{}')",
+                self.command(),
+                version
+            );
+        }
+        Ok(version)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Linter {
+    #[value(name = "eslint")]
     EsLint,
     Biome,
     None,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Formatter {
     Prettier,
     Biome,
     None,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum TestTool {
     Jest,
     Vitest,
     None,
 }
 
-/// Generate ProjectConfig interactively.
+#[derive(Debug, Args)]
+pub struct InitArgs {
+    /// Target directory for the new project (created when it does not exist); `.` for the current directory
+    pub directory: String,
+
+    /// Project name (defaults to the name of the target directory)
+    #[arg(long)]
+    pub name: Option<String>,
+
+    /// Package manager used to install dependencies
+    #[arg(long, value_enum)]
+    pub package_manager: Option<PackageManager>,
+
+    /// Linter to configure
+    #[arg(long, value_enum)]
+    pub linter: Option<Linter>,
+
+    /// Formatter to configure
+    #[arg(long, value_enum)]
+    pub formatter: Option<Formatter>,
+
+    /// Test tool to configure
+    #[arg(long, value_enum)]
+    pub test_tool: Option<TestTool>,
+
+    /// Use a preset from the user config file (~/.config/ts-cdk/config.toml)
+    #[arg(long, value_name = "NAME")]
+    pub preset: Option<String>,
+
+    /// Local template directory layered over the embedded templates, file by file
+    #[arg(long, value_name = "DIR")]
+    pub template_dir: Option<PathBuf>,
+
+    /// Template pack in a git repository, e.g. `git+https://example.com/starters.git#v2`
+    #[arg(long, value_name = "URL")]
+    pub template: Option<String>,
+
+    /// Answer a prompt of the template pack's `template.toml` (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_variable)]
+    pub variables: Vec<(String, String)>,
+
+    /// Read answers from a file (.toml, .json, .yaml) instead of prompting
+    #[arg(long, value_name = "FILE")]
+    pub answers: Option<PathBuf>,
+
+    /// Write the collected answers to a file (.toml, .json, .yaml)
+    #[arg(long, value_name = "FILE")]
+    pub save_answers: Option<PathBuf>,
+
+    /// Print the files that would be generated without writing anything or installing dependencies
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// With --dry-run, also print the full content of every file
+    #[arg(long, requires = "dry_run")]
+    pub show_contents: bool,
+
+    /// Do not install dependencies after generating the project
+    #[arg(long)]
+    pub skip_install: bool,
+
+    /// Do not run the hooks of the template pack
+    #[arg(long)]
+    pub skip_hooks: bool,
+
+    /// Install exactly what the lockfile specifies (`npm ci`, `yarn install --immutable`,
+    /// `pnpm install --frozen-lockfile`)
+    #[arg(long, visible_alias = "ci", conflicts_with = "skip_install")]
+    pub frozen_lockfile: bool,
+
+    /// Pin `packageManager` in package.json to the version of the package manager on PATH
+    #[arg(long)]
+    pub pin_package_manager: bool,
+
+    /// Overwrite existing files in the target directory without asking
+    #[arg(long, conflicts_with = "skip_existing")]
+    pub force: bool,
+
+    /// Keep existing files in the target directory without asking
+    #[arg(long)]
+    pub skip_existing: bool,
+
+    /// Do not prompt; use the default for any value not given on the command line
+    #[arg(short, long)]
+    pub yes: bool,
+}
+
+impl InitArgs {
+    /// Whether prompts may be shown: stdin is a terminal and `--yes` is not given.
+    pub fn is_interactive(&self) -> bool {
+        !self.yes && std::io::stdin().is_terminal()
+    }
+
+    /// How to handle files that already exist in the target directory.
+    pub fn conflict_strategy(&self) -> ConflictStrategy {
+        if self.force {
+            ConflictStrategy::Overwrite
+        } else if self.skip_existing {
+            ConflictStrategy::Skip
+        } else {
+            ConflictStrategy::Ask
+        }
+    }
+}
+
+/// Generate ProjectConfig from the command line, prompting for missing values.
+///
+/// Prompts are only shown when stdin is a terminal and `--yes` is not given.
+/// Otherwise every missing value falls back to its default (the first choice).
+/// When `--answers` is given, the answers file is used and command line values override it;
+/// `--preset` is then ignored. Otherwise values from `--preset` apply to anything not
+/// given on the command line.
+/// The prompts declared by the template manifest are asked last.
 ///
 /// ### Parameters
-/// - `args` - Arguments from the command line(target directory path)
+/// - `args` - Arguments of the `init` command
+/// - `user_config` - User configuration holding the presets
+/// - `manifest` - Manifest of the template pack
 ///
 /// ### Returns
 /// - `ProjectConfig` - ProjectConfig
-pub fn generate_project_config(args: &Vec<String>) -> Result<ProjectConfig> {
-    let target_dir_path = convert_to_dir_path(&args)?;
+pub fn generate_project_config(
+    args: &InitArgs,
+    user_config: &UserConfig,
+    manifest: &TemplateManifest,
+) -> Result<ProjectConfig> {
+    let target_dir_path = resolve_target_dir(&args.directory)?;
+    let default_name = default_project_name(&target_dir_path, &std::env::current_dir()?);
 
-    let name = Input::<String>::new()
-        .with_prompt("Project name")
-        .interact()?;
+    let preset = match &args.preset {
+        Some(name) => user_config.preset(name)?.clone(),
+        None => Preset::default(),
+    };
 
-    // NOTE: select package manager
-    let package_managers = &["npm", "yarn", "pnpm"];
-    let package_manager = Select::new()
-        .with_prompt("Choose a package manager")
-        .items(package_managers)
-        .default(0)
-        .interact()?;
+    if let Some(path) = &args.answers {
+        let answers = answers::load_answers(path)?;
+        let name = args.name.clone().unwrap_or(answers.name);
+        check_project_name(&name)?;
+        let variables = prompt_variables(manifest, answers.variables, &args.variables, false)?;
+        let package_manager = args.package_manager.unwrap_or(answers.package_manager);
+        // NOTE: a pinned version only applies to the package manager it was read from.
+        let package_manager_version = match args.pin_package_manager {
+            true => Some(package_manager.installed_version()?),
+            false if package_manager == answers.package_manager => answers.package_manager_version,
+            false => None,
+        };
+        return Ok(ProjectConfig {
+            target_dir_path,
+            name,
+            package_manager,
+            linter: args.linter.unwrap_or(answers.linter),
+            formatter: args.formatter.unwrap_or(answers.formatter),
+            test_tool: args.test_tool.unwrap_or(answers.test_tool),
+            package_manager_version,
+            variables,
+        });
+    }
 
-    // NOTE: select linter
-    let linters = &["eslint", "biome", "none"];
-    let linter = Select::new()
-        .with_prompt("Choose a linter")
-        .items(linters)
-        .default(0)
-        .interact()?;
+    let package_manager = args.package_manager.or(preset.package_manager);
+    let linter = args.linter.or(preset.linter);
+    let formatter = args.formatter.or(preset.formatter);
+    let test_tool = args.test_tool.or(preset.test_tool);
 
-    // NOTE: select formatter
-    let formatters = &["prettier", "biome", "none"];
-    let formatter = Select::new()
-        .with_prompt("Choose a formatter")
-        .items(formatters)
-        .default(0)
-        .interact()?;
+    let interactive = args.is_interactive();
 
-    // NOTE: select test tools
-    let test_tools = &["jest", "vitest", "none"];
-    let test_tool = Select::new()
-        .with_prompt("Choose a test tool")
-        .items(test_tools)
-        .default(0)
-        .interact()?;
+    let name = match (&args.name, default_name) {
+        (Some(name), _) => check_project_name(name)?,
+        (None, default_name) if interactive => {
+            let mut input = Input::<String>::new()
+                .with_prompt("Project name")
+                .validate_with(|input: &String| validate_project_name(input));
+            if let Some(default_name) = default_name {
+                input = input.with_initial_text(default_name);
+            }
+            input.interact_text()?
+        }
+        (None, Some(default_name)) => check_project_name(&default_name)?,
+        (None, None) => anyhow::bail!(
+            "Cannot derive a project name from '{}' (use --name)",
+            target_dir_path.display()
+        ),
+    };
+
+    let package_manager = select_value(package_manager, "Choose a package manager", interactive)?;
+    let linter = select_value(linter, "Choose a linter", interactive)?;
+    let formatter = select_value(formatter, "Choose a formatter", interactive)?;
+    let test_tool = select_value(test_tool, "Choose a test tool", interactive)?;
+    let variables = prompt_variables(manifest, BTreeMap::new(), &args.variables, interactive)?;
+    let package_manager_version = match args.pin_package_manager {
+        true => Some(package_manager.installed_version()?),
+        false => None,
+    };
 
     println!("name: {}", name);
-    println!("package_manager: {}", package_managers[package_manager]);
-    println!("linter: {}", linters[linter]);
-    println!("formatter: {}", formatters[formatter]);
-    println!("test_tool: {}", test_tools[test_tool]);
+    println!("package_manager: {}", value_name(&package_manager));
+    if let Some(version) = &package_manager_version {
+        println!("package_manager_version: {}", version);
+    }
+    println!("linter: {}", value_name(&linter));
+    println!("formatter: {}", value_name(&formatter));
+    println!("test_tool: {}", value_name(&test_tool));
+    for (name, value) in &variables {
+        println!("{}: {}", name, value);
+    }
 
     Ok(ProjectConfig {
         target_dir_path,
         name,
-        package_manager: match package_managers[package_manager] {
-            "npm" => PackageManager::Npm,
-            "yarn" => PackageManager::Yarn,
-            "pnpm" => PackageManager::Pnpm,
-            _ => unreachable!(),
-        },
-        linter: match linters[linter] {
-            "eslint" => Linter::EsLint,
-            "biome" => Linter::Biome,
-            "none" => Linter::None,
-            _ => unreachable!(),
-        },
-        test_tool: match test_tools[test_tool] {
-            "jest" => TestTool::Jest,
-            "vitest" => TestTool::Vitest,
-            "none" => TestTool::None,
-            _ => unreachable!(),
-        },
-        formatter: match formatters[formatter] {
-            "prettier" => Formatter::Prettier,
-            "biome" => Formatter::Biome,
-            "none" => Formatter::None,
-            _ => unreachable!(),
-        },
+        package_manager,
+        linter,
+        formatter,
+        test_tool,
+        package_manager_version,
+        variables,
     })
 }
 
-/// Install dependencies.
+/// Answer the prompts of the template manifest.
+///
+/// `--var` values override the answers file; anything else is prompted for, or falls back
+/// to its default when prompts are not shown. Answers to unknown prompts are dropped.
 ///
 /// ### Parameters
-/// - `config` - ProjectConfig
-pub fn install_dependencies(config: &ProjectConfig) -> Result<()> {
-    match config.package_manager {
-        PackageManager::Npm => {
-            std::process::Command::new("npm")
-                .arg("install")
-                .current_dir(&config.target_dir_path)
-                .status()?;
-        }
-        PackageManager::Yarn => {
-            std::process::Command::new("yarn")
-                .arg("install")
-                .current_dir(&config.target_dir_path)
-                .status()?;
+/// - `manifest` - Manifest of the template pack
+/// - `answers` - Answers from the answers file
+/// - `cli_values` - `--var` values
+/// - `interactive` - Whether prompts may be shown
+///
+/// ### Returns
+/// - `BTreeMap<String, VariableValue>` - Answer of every prompt
+pub fn prompt_variables(
+    manifest: &TemplateManifest,
+    mut answers: BTreeMap<String, VariableValue>,
+    cli_values: &[(String, String)],
+    interactive: bool,
+) -> Result<BTreeMap<String, VariableValue>> {
+    for (name, raw) in cli_values {
+        let prompt = manifest
+            .prompts
+            .iter()
+            .find(|prompt| &prompt.name == name)
+            .ok_or_else(|| {
+                let names = manifest
+                    .prompts
+                    .iter()
+                    .map(|prompt| prompt.name.as_str())
+                    .collect::<Vec<_>>();
+                anyhow::anyhow!(
+                    "Unknown template variable '{}' (available: {})",
+                    name,
+                    if names.is_empty() {
+                        "none".to_string()
+                    } else {
+                        names.join(", ")
+                    }
+                )
+            })?;
+        let value = prompt
+            .parse_value(raw)
+            .map_err(|reason| anyhow::anyhow!("Invalid value of --var {}: {}", name, reason))?;
+        answers.insert(name.clone(), value);
+    }
+
+    let mut variables = BTreeMap::new();
+    for prompt in &manifest.prompts {
+        let value = match answers.remove(&prompt.name) {
+            Some(value) => {
+                prompt.check(&value).map_err(|reason| {
+                    anyhow::anyhow!("Invalid answer to '{}': {}", prompt.name, reason)
+                })?;
+                value
+            }
+            None if interactive => ask_prompt(prompt)?,
+            None => prompt.fallback().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "'{}' has no default (use --var {}=VALUE)",
+                    prompt.name,
+                    prompt.name
+                )
+            })?,
+        };
+        variables.insert(prompt.name.clone(), value);
+    }
+    Ok(variables)
+}
+
+/// Show a prompt of the template manifest.
+fn ask_prompt(prompt: &PromptDefinition) -> Result<VariableValue> {
+    let value = match prompt.kind {
+        PromptKind::String => {
+            let mut input = Input::<String>::new()
+                .with_prompt(&prompt.message)
+                .validate_with(|input: &String| {
+                    prompt.check(&VariableValue::String(input.clone()))
+                });
+            if let Some(VariableValue::String(default)) = &prompt.default {
+                input = input.default(default.clone());
+            }
+            VariableValue::String(input.interact_text()?)
         }
-        PackageManager::Pnpm => {
-            std::process::Command::new("pnpm")
-                .arg("install")
-                .current_dir(&config.target_dir_path)
-                .status()?;
+        PromptKind::Bool => VariableValue::Bool(
+            Confirm::new()
+                .with_prompt(&prompt.message)
+                .default(prompt.default == Some(VariableValue::Bool(true)))
+                .interact()?,
+        ),
+        PromptKind::Select => {
+            let default = prompt
+                .choices
+                .iter()
+                .position(|choice| prompt.default == Some(VariableValue::String(choice.clone())))
+                .unwrap_or(0);
+            let index = Select::new()
+                .with_prompt(&prompt.message)
+                .items(&prompt.choices)
+                .default(default)
+                .interact()?;
+            VariableValue::String(prompt.choices[index].clone())
         }
+    };
+    Ok(value)
+}
+
+/// Parse a `--var NAME=VALUE` argument.
+pub fn parse_variable(raw: &str) -> Result<(String, String), String> {
+    raw.split_once('=')
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .ok_or_else(|| format!("expected NAME=VALUE, got '{}'", raw))
+}
+
+/// Templates given to `init`.
+///
+/// ### Parameters
+/// - `args` - Arguments of the `init` command
+///
+/// ### Returns
+/// - `TemplateSources` - `--template-dir` made absolute, and `--template`
+pub fn template_sources(args: &InitArgs) -> Result<TemplateSources> {
+    let template_dir = match &args.template_dir {
+        Some(dir) => Some(std::fs::canonicalize(dir).map_err(|_| {
+            anyhow::anyhow!("Template directory '{}' does not exist", dir.display())
+        })?),
+        None => None,
+    };
+    Ok(TemplateSources {
+        template_dir,
+        template: args.template.clone(),
+    })
+}
+
+/// Create the template loader: `--template-dir` first, then the `--template` pack,
+/// then the `template_dir` of the user configuration, then the embedded templates.
+///
+/// ### Parameters
+/// - `sources` - Templates given to `init`
+/// - `user_config` - User configuration
+///
+/// ### Returns
+/// - `TemplateLoader` - TemplateLoader
+pub fn template_loader(
+    sources: &TemplateSources,
+    user_config: &UserConfig,
+) -> Result<TemplateLoader> {
+    let template_pack = match &sources.template {
+        Some(spec) => Some(GitTemplate::parse(spec)?.fetch()?),
+        None => None,
+    };
+    let layers = sources
+        .template_dir
+        .iter()
+        .chain(template_pack.iter())
+        .chain(user_config.template_dir.iter())
+        .cloned()
+        .collect();
+    TemplateLoader::new(layers)
+}
+
+/// Make sure `--frozen-lockfile` can succeed: the project must have a lockfile, shipped by
+/// the template pack or already in the target directory.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `files` - Generated files
+pub fn check_lockfile(config: &ProjectConfig, files: &[TemplateFile]) -> Result<()> {
+    let lockfiles = config.package_manager.lockfiles();
+    let found = lockfiles.iter().any(|lockfile| {
+        files.iter().any(|file| file.file_path == *lockfile)
+            || config.target_dir_path.join(lockfile).is_file()
+    });
+    if !found {
+        anyhow::bail!(
+            "--frozen-lockfile needs {}, but the templates do not generate it and {} does not contain it. \
+             Add it to your template pack, or drop --frozen-lockfile",
+            lockfiles[0],
+            config.target_dir_path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Install dependencies.
+///
+/// The output of the package manager is shown as it runs and, when it fails, saved to a log file.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `frozen_lockfile` - Install exactly what the lockfile specifies (CI mode)
+pub fn install_dependencies(config: &ProjectConfig, frozen_lockfile: bool) -> Result<()> {
+    let command = config.package_manager.command();
+    let program = which::which(command).map_err(|_| {
+        anyhow::anyhow!(
+            "'{}' was not found on PATH. Install it, or re-run with --skip-install and install dependencies yourself",
+            command
+        )
+    })?;
+    let install_args = config.package_manager.install_args(frozen_lockfile);
+    let description = format!("{} {}", command, install_args.join(" "));
+
+    println!("Installing dependencies with `{}`...", description);
+
+    run_logged(
+        Command::new(program)
+            .args(install_args)
+            .current_dir(&config.target_dir_path),
+        &description,
+        "ts-cdk-install-",
+    )
+}
+
+/// Offer to remove the generated project after installing dependencies failed.
+///
+/// ### Parameters
+/// - `project` - What was written by `write_template_files`
+/// - `interactive` - Whether prompts may be shown, nothing is removed otherwise
+pub fn offer_cleanup(project: &WrittenProject, interactive: bool) -> Result<()> {
+    if !interactive {
+        return Ok(());
+    }
+    let prompt = if project.created_target_dir {
+        format!(
+            "Remove the generated project at {}?",
+            project.target_dir.display()
+        )
+    } else {
+        format!(
+            "Installing dependencies failed. Remove the {} files created in {}?",
+            project.created_files.len(),
+            project.target_dir.display()
+        )
+    };
+    if Confirm::new()
+        .with_prompt(prompt)
+        .default(false)
+        .interact()?
+    {
+        project.remove()?;
     }
     Ok(())
 }
 
-fn convert_to_dir_path(args: &Vec<String>) -> Result<String> {
-    let raw_path = if args.is_empty() { "." } else { &args[0] }.to_string();
+/// Select a value, prompting only when it was not given on the command line.
+///
+/// ### Parameters
+/// - `value` - Value given on the command line
+/// - `prompt` - Prompt shown to the user
+/// - `interactive` - Whether prompts may be shown
+///
+/// ### Returns
+/// - `T` - Given value, selected value, or the first variant as default
+fn select_value<T: ValueEnum + Clone>(
+    value: Option<T>,
+    prompt: &str,
+    interactive: bool,
+) -> Result<T> {
+    if let Some(value) = value {
+        return Ok(value);
+    }
+    let variants = T::value_variants();
+    if !interactive {
+        return Ok(variants[0].clone());
+    }
+    let items: Vec<String> = variants.iter().map(value_name).collect();
+    let index = Select::new()
+        .with_prompt(prompt)
+        .items(&items)
+        .default(0)
+        .interact()?;
+    Ok(variants[index].clone())
+}
+
+/// Name of a value as written on the command line (e.g. `eslint`).
+pub fn value_name<T: ValueEnum>(value: &T) -> String {
+    value
+        .to_possible_value()
+        .map(|value| value.get_name().to_string())
+        .unwrap_or_default()
+}
 
-    // Check references to parent directories.
-    if raw_path.contains("../") {
-        anyhow::bail!("Reference to parent directories not permitted");
+/// Validate a project name that was not entered at the prompt.
+fn check_project_name(name: &str) -> Result<String> {
+    validate_project_name(name)
+        .map_err(|reason| anyhow::anyhow!("Invalid project name '{}': {}", name, reason))?;
+    Ok(name.to_string())
+}
+
+/// Resolve the target directory given on the command line.
+///
+/// Absolute and relative paths are accepted, but references to parent directories (`..`)
+/// are not. `.` components are dropped, so `./my-app/` becomes `my-app`.
+///
+/// ### Parameters
+/// - `raw_path` - Target directory as given on the command line
+///
+/// ### Returns
+/// - `PathBuf` - Target directory, `.` for the current directory
+fn resolve_target_dir(raw_path: &str) -> Result<PathBuf> {
+    let mut target_dir = PathBuf::new();
+    for component in Path::new(raw_path).components() {
+        match component {
+            Component::ParentDir => anyhow::bail!(
+                "Reference to parent directories not permitted: '{}'",
+                raw_path
+            ),
+            Component::CurDir => {}
+            Component::Prefix(_) | Component::RootDir | Component::Normal(_) => {
+                target_dir.push(component.as_os_str())
+            }
+        }
     }
 
-    let target_dir_path = raw_path.replace("./", "").trim_end_matches('/').to_string();
+    if target_dir.as_os_str().is_empty() {
+        target_dir.push(".");
+    }
 
-    Ok(target_dir_path)
+    Ok(target_dir)
+}
+
+/// Default project name: the name of the target directory in kebab case, e.g. `my-app` for `MyApp`.
+///
+/// ### Parameters
+/// - `target_dir` - Target directory returned by `resolve_target_dir`
+/// - `current_dir` - Current directory, the target directory when it is `.`
+fn default_project_name(target_dir: &Path, current_dir: &Path) -> Option<String> {
+    let dir_name = if target_dir == Path::new(".") {
+        current_dir.file_name()?
+    } else {
+        target_dir.file_name()?
+    };
+    dir_name
+        .to_str()
+        .map(|dir_name| Naming::new(dir_name).kebab)
+        .filter(|name| !name.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_target_dirs() {
+        let cases = [
+            ("my-app", "my-app"),
+            ("./my-app/", "my-app"),
+            ("foo../bar", "foo../bar"),
+            ("a./b", "a./b"),
+            (".", "."),
+            ("./", "."),
+            ("/tmp/my-app", "/tmp/my-app"),
+        ];
+        for (raw_path, target_dir) in cases {
+            assert_eq!(
+                resolve_target_dir(raw_path).unwrap(),
+                PathBuf::from(target_dir),
+                "{}",
+                raw_path
+            );
+        }
+        for raw_path in ["..", "../my-app", "a/../b"] {
+            assert!(resolve_target_dir(raw_path).is_err(), "{}", raw_path);
+        }
+    }
+
+    #[test]
+    fn derives_project_names_from_target_dirs() {
+        let current_dir = Path::new("/home/user/MyApp");
+        let cases = [
+            (".", Some("my-app")),
+            ("foo../bar", Some("bar")),
+            ("a./b", Some("b")),
+            ("projects/CdkStack", Some("cdk-stack")),
+            ("/tmp/my_app", Some("my-app")),
+        ];
+        for (raw_path, name) in cases {
+            let target_dir = resolve_target_dir(raw_path).unwrap();
+            assert_eq!(
+                default_project_name(&target_dir, current_dir).as_deref(),
+                name,
+                "{}",
+                raw_path
+            );
+        }
+        assert_eq!(default_project_name(Path::new("."), Path::new("/")), None);
+    }
 }
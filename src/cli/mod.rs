@@ -1 +1,35 @@
+pub mod add;
+pub mod answers;
+pub mod conflict;
+pub mod dry_run;
+pub mod hooks;
 pub mod init;
+pub mod process;
+pub mod project_name;
+pub mod template;
+pub mod user_config;
+
+use clap::{Parser, Subcommand};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "ts-cdk",
+    version,
+    about = "CLI tool for TypeScript + AWS CDK project.",
+    after_help = "Example:\n    `ts-cdk init my-project`    Create a new project in 'my-project' directory\n    `ts-cdk template check --template-dir ./my-templates`    Check a template directory"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Create a new TypeScript + AWS CDK project in the specified directory
+    Init(init::InitArgs),
+    /// Add a component to the project in the current directory
+    Add(add::AddArgs),
+    /// Tools for template authors
+    #[command(subcommand)]
+    Template(template::TemplateCommand),
+}
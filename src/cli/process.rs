@@ -0,0 +1,78 @@
+use anyhow::{Context, Result};
+use std::fs::File;
+use std::io::{Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+/// Run a command, showing its output as it runs and saving it to a log file when it fails.
+///
+/// ### Parameters
+/// - `command` - Command to run
+/// - `description` - How the command is named in messages, e.g. `npm install`
+/// - `log_prefix` - Prefix of the log file name, e.g. `ts-cdk-install-`
+pub fn run_logged(command: &mut Command, description: &str, log_prefix: &str) -> Result<()> {
+    let mut log_file = tempfile::Builder::new()
+        .prefix(log_prefix)
+        .suffix(".log")
+        .tempfile()?;
+    let log = Arc::new(Mutex::new(log_file.reopen()?));
+
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run `{}`", description))?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .map(|stdout| tee(stdout, std::io::stdout(), Arc::clone(&log)));
+    let stderr = child
+        .stderr
+        .take()
+        .map(|stderr| tee(stderr, std::io::stderr(), Arc::clone(&log)));
+    let status = child.wait()?;
+    for handle in [stdout, stderr].into_iter().flatten() {
+        let _ = handle.join();
+    }
+
+    if !status.success() {
+        log_file.flush()?;
+        let (_, log_path) = log_file.keep()?;
+        anyhow::bail!(
+            "`{}` failed ({}). Output was saved to {}",
+            description,
+            status,
+            log_path.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Copy everything from `reader` to `terminal` and the shared log file.
+fn tee<R, W>(
+    mut reader: R,
+    mut terminal: W,
+    log: Arc<Mutex<File>>,
+) -> JoinHandle<std::io::Result<()>>
+where
+    R: Read + Send + 'static,
+    W: Write + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let mut buffer = [0; 8192];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                return Ok(());
+            }
+            terminal.write_all(&buffer[..read])?;
+            terminal.flush()?;
+            if let Ok(mut log) = log.lock() {
+                log.write_all(&buffer[..read])?;
+            }
+        }
+    })
+}
@@ -0,0 +1,150 @@
+// NOTE: npm rejects package names longer than this.
+const MAX_PACKAGE_NAME_LENGTH: usize = 214;
+// NOTE: CloudFormation rejects stack names longer than this.
+const MAX_STACK_NAME_LENGTH: usize = 128;
+
+/// Check a project name against the npm package name rules and the identifiers generated from it.
+///
+/// A scoped name (`@scope/name`) is accepted; only `name` is used for class and file names
+/// (see `unscoped_name`).
+///
+/// ### Parameters
+/// - `name` - Project name
+///
+/// ### Returns
+/// - `Err(String)` - Reason why the name is invalid
+pub fn validate_project_name(name: &str) -> Result<(), String> {
+    if name.is_empty() {
+        return Err("the project name cannot be empty".to_string());
+    }
+    if name.trim() != name {
+        return Err("the project name cannot start or end with spaces".to_string());
+    }
+    if name.len() > MAX_PACKAGE_NAME_LENGTH {
+        return Err(format!(
+            "npm package names cannot be longer than {} characters",
+            MAX_PACKAGE_NAME_LENGTH
+        ));
+    }
+    if name.chars().any(|c| c.is_uppercase()) {
+        return Err("npm package names cannot contain uppercase letters".to_string());
+    }
+
+    let package_name = match name.strip_prefix('@') {
+        Some(scoped_name) => {
+            let (scope, package_name) = scoped_name
+                .split_once('/')
+                .ok_or_else(|| "scoped names must look like @scope/name".to_string())?;
+            validate_url_safe(scope, "scope")?;
+            package_name
+        }
+        None => name,
+    };
+    validate_url_safe(package_name, "package name")?;
+    if package_name.starts_with('.') || package_name.starts_with('_') {
+        return Err("npm package names cannot start with '.' or '_'".to_string());
+    }
+    if matches!(package_name, "node_modules" | "favicon.ico") {
+        return Err(format!("'{}' is a reserved npm package name", package_name));
+    }
+
+    // NOTE: the name is also used for the stack class (`%ProjectName%Stack`) and stack id.
+    if !package_name.starts_with(|c: char| c.is_ascii_lowercase()) {
+        return Err(
+            "the name must start with a letter to be usable as a class name and stack id"
+                .to_string(),
+        );
+    }
+    if let Some(c) = package_name
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || *c == '-' || *c == '_'))
+    {
+        return Err(format!(
+            "'{}' cannot be used in class names and stack ids (use letters, digits, '-' or '_')",
+            c
+        ));
+    }
+    let stack_name_length = package_name
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .count()
+        + "Stack".len();
+    if stack_name_length > MAX_STACK_NAME_LENGTH {
+        return Err(format!(
+            "the stack id would be longer than {} characters",
+            MAX_STACK_NAME_LENGTH
+        ));
+    }
+
+    Ok(())
+}
+
+/// Name without the npm scope (`@scope/name` becomes `name`).
+pub fn unscoped_name(name: &str) -> &str {
+    match name.strip_prefix('@') {
+        Some(scoped_name) => scoped_name
+            .split_once('/')
+            .map_or(scoped_name, |(_, package_name)| package_name),
+        None => name,
+    }
+}
+
+fn validate_url_safe(part: &str, label: &str) -> Result<(), String> {
+    if part.is_empty() {
+        return Err(format!("the {} cannot be empty", label));
+    }
+    match part
+        .chars()
+        .find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')))
+    {
+        Some(c) => Err(format!(
+            "'{}' is not allowed in an npm {} (use letters, digits, '-', '.', '_' or '~')",
+            c, label
+        )),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_valid_names() {
+        for name in ["my-app", "app2", "my_app", "@acme/my-app", "@a.b/c"] {
+            assert_eq!(validate_project_name(name), Ok(()), "{}", name);
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        let long_name = "a".repeat(MAX_PACKAGE_NAME_LENGTH + 1);
+        let long_stack_name = "a".repeat(MAX_STACK_NAME_LENGTH);
+        let cases = [
+            ("", "cannot be empty"),
+            (" my-app", "spaces"),
+            (long_name.as_str(), "longer than 214"),
+            ("MyApp", "uppercase"),
+            ("@acme", "@scope/name"),
+            ("@/my-app", "scope cannot be empty"),
+            ("@acme/", "package name cannot be empty"),
+            ("my app", "' ' is not allowed"),
+            ("_app", "cannot start with '.' or '_'"),
+            ("node_modules", "reserved"),
+            ("1app", "start with a letter"),
+            ("my.app", "'.' cannot be used in class names"),
+            (long_stack_name.as_str(), "stack id would be longer"),
+        ];
+        for (name, reason) in cases {
+            let error = validate_project_name(name).unwrap_err();
+            assert!(error.contains(reason), "{:?}: {}", name, error);
+        }
+    }
+
+    #[test]
+    fn strips_the_scope() {
+        assert_eq!(unscoped_name("@acme/my-app"), "my-app");
+        assert_eq!(unscoped_name("my-app"), "my-app");
+        assert_eq!(unscoped_name("@acme"), "acme");
+    }
+}
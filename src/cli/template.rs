@@ -0,0 +1,267 @@
+use crate::cli::add::{sample_contexts, ADD_TEMPLATES};
+use crate::cli::hooks::plan_hooks;
+use crate::cli::init::{
+    parse_variable, prompt_variables, value_name, Formatter, Linter, PackageManager, ProjectConfig,
+    TestTool,
+};
+use crate::templates::generate::{generate_template_files, TemplateFile};
+use crate::templates::git::GitTemplate;
+use crate::templates::loader::TemplateLoader;
+use crate::templates::registry::registered_templates;
+use crate::templates::render::render_template;
+use crate::templates::template_manifest::TEMPLATE_MANIFEST_FILE_NAME;
+use crate::templates::validate::{format_problem, validate_file};
+
+use anyhow::Result;
+use clap::{Args, Subcommand, ValueEnum};
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::PathBuf;
+
+#[derive(Debug, Subcommand)]
+pub enum TemplateCommand {
+    /// Render templates with every combination of options and report problems
+    Check(CheckArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct CheckArgs {
+    /// Template directory to check (the embedded templates when not given)
+    #[arg(long, value_name = "DIR", conflicts_with = "template")]
+    pub template_dir: Option<PathBuf>,
+
+    /// Template pack in a git repository to check
+    #[arg(long, value_name = "URL")]
+    pub template: Option<String>,
+
+    /// Answer a prompt of the template pack's `template.toml` (repeatable)
+    #[arg(long = "var", value_name = "NAME=VALUE", value_parser = parse_variable)]
+    pub variables: Vec<(String, String)>,
+}
+
+/// Result of rendering the templates for one combination of options.
+struct CheckResult {
+    config: ProjectConfig,
+    problems: Vec<String>,
+}
+
+/// Check templates for template authors.
+///
+/// The templates are rendered with every combination of package manager, linter,
+/// formatter and test tool; every generated file is validated like `init` does, and so
+/// are the templates of the `add` commands rendered for sample components.
+/// Missing templates, render errors and invalid files fail the check; template files
+/// that no combination uses are only reported.
+///
+/// ### Parameters
+/// - `args` - Arguments of the `template check` command
+pub fn check_templates(args: &CheckArgs) -> Result<()> {
+    let template_dir = match (&args.template_dir, &args.template) {
+        (Some(dir), _) => Some(dir.clone()),
+        (None, Some(spec)) => Some(GitTemplate::parse(spec)?.fetch()?),
+        (None, None) => None,
+    };
+    let loader = TemplateLoader::new(template_dir.iter().cloned().collect())?;
+    let manifest = loader.manifest();
+    let variables = prompt_variables(manifest, BTreeMap::new(), &args.variables, false)?;
+
+    println!(
+        "Checking {}",
+        template_dir
+            .as_ref()
+            .map(|dir| dir.display().to_string())
+            .unwrap_or_else(|| "embedded templates".to_string())
+    );
+
+    let referenced = registered_templates()
+        .chain(ADD_TEMPLATES.iter().copied())
+        .map(str::to_string)
+        .chain(manifest.files.iter().map(|rule| rule.path.clone()))
+        .collect::<BTreeSet<_>>();
+    let missing = referenced
+        .iter()
+        .filter(|path| !loader.exists(path))
+        .collect::<Vec<_>>();
+    let unused = loader
+        .template_paths()?
+        .into_iter()
+        .filter(|path| !referenced.contains(path) && path != TEMPLATE_MANIFEST_FILE_NAME)
+        .collect::<Vec<_>>();
+
+    if !missing.is_empty() {
+        println!();
+        println!("Missing templates:");
+        for path in &missing {
+            println!("  - {}", path);
+        }
+    }
+    if !unused.is_empty() {
+        println!();
+        println!("Unused templates:");
+        for path in &unused {
+            println!("  - {}", path);
+        }
+    }
+
+    let mut results = Vec::new();
+    for &package_manager in PackageManager::value_variants() {
+        for &linter in Linter::value_variants() {
+            for &formatter in Formatter::value_variants() {
+                for &test_tool in TestTool::value_variants() {
+                    let config = ProjectConfig {
+                        target_dir_path: PathBuf::from("."),
+                        name: "my-app".to_string(),
+                        package_manager,
+                        linter,
+                        formatter,
+                        test_tool,
+                        package_manager_version: None,
+                        variables: variables.clone(),
+                    };
+                    let problems = check_combination(&config, &loader);
+                    results.push(CheckResult { config, problems });
+                }
+            }
+        }
+    }
+
+    println!();
+    print_matrix(&results);
+
+    // NOTE: most problems show up in many combinations, so each is listed once.
+    let mut problems: BTreeMap<&str, usize> = BTreeMap::new();
+    for problem in results.iter().flat_map(|result| &result.problems) {
+        *problems.entry(problem).or_default() += 1;
+    }
+    if !problems.is_empty() {
+        println!();
+        println!("Problems:");
+        for (problem, count) in &problems {
+            println!(
+                "  - {} ({} of {} combinations)",
+                problem,
+                count,
+                results.len()
+            );
+        }
+    }
+
+    let failed = results
+        .iter()
+        .filter(|result| !result.problems.is_empty())
+        .count();
+    println!();
+    println!(
+        "{} combinations: {} ok, {} failed",
+        results.len(),
+        results.len() - failed,
+        failed
+    );
+
+    if failed > 0 || !missing.is_empty() {
+        anyhow::bail!("Template check failed");
+    }
+    Ok(())
+}
+
+/// Render and validate the templates for one combination of options.
+fn check_combination(config: &ProjectConfig, loader: &TemplateLoader) -> Vec<String> {
+    let files = match generate_template_files(config, loader) {
+        Ok(files) => files,
+        Err(e) => return vec![format!("{:#}", e)],
+    };
+    let mut problems = files
+        .iter()
+        .flat_map(|file| {
+            validate_file(file)
+                .into_iter()
+                .map(move |problem| format_problem(file, &problem))
+        })
+        .collect::<Vec<_>>();
+    if let Err(e) = plan_hooks(config, loader.manifest()) {
+        problems.push(format!("{:#}", e));
+    }
+    match check_add_templates(config, loader) {
+        Ok(add_problems) => problems.extend(add_problems),
+        Err(e) => problems.push(format!("{:#}", e)),
+    }
+    problems
+}
+
+/// Render and validate the templates of the `add` commands for one combination of options.
+///
+/// Missing templates are skipped; they are reported once by `check_templates`.
+fn check_add_templates(config: &ProjectConfig, loader: &TemplateLoader) -> Result<Vec<String>> {
+    let mut problems = Vec::new();
+    for (template_path, context) in sample_contexts(config)? {
+        if !loader.exists(template_path) {
+            continue;
+        }
+        let source = loader.load(template_path)?;
+        let content = match render_template(template_path, &source, &context) {
+            Ok(content) => content,
+            Err(e) => {
+                problems.push(format!("{:#}", e));
+                continue;
+            }
+        };
+        let file = TemplateFile {
+            file_path: template_path.to_string(),
+            content,
+            template: Some(template_path.to_string()),
+        };
+        problems.extend(
+            validate_file(&file)
+                .iter()
+                .map(|problem| format_problem(&file, problem)),
+        );
+    }
+    Ok(problems)
+}
+
+fn print_matrix(results: &[CheckResult]) {
+    let header = [
+        "package_manager",
+        "linter",
+        "formatter",
+        "test_tool",
+        "result",
+    ];
+    let rows = results
+        .iter()
+        .map(|result| {
+            [
+                value_name(&result.config.package_manager),
+                value_name(&result.config.linter),
+                value_name(&result.config.formatter),
+                value_name(&result.config.test_tool),
+                match result.problems.len() {
+                    0 => "ok".to_string(),
+                    1 => "FAILED (1 problem)".to_string(),
+                    count => format!("FAILED ({} problems)", count),
+                },
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    let widths = header.map(str::len);
+    let widths = rows.iter().fold(widths, |mut widths, row| {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+        widths
+    });
+    let print_row = |cells: &[String]| {
+        let line = cells
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect::<Vec<_>>()
+            .join("  ");
+        println!("{}", line.trim_end());
+    };
+
+    print_row(&header.map(str::to_string));
+    for row in &rows {
+        print_row(row);
+    }
+}
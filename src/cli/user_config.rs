@@ -0,0 +1,112 @@
+use crate::cli::init::{Formatter, Linter, PackageManager, TestTool};
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// User-level configuration read from `~/.config/ts-cdk/config.toml`.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct UserConfig {
+    /// Named sets of default answers
+    #[serde(default)]
+    pub presets: BTreeMap<String, Preset>,
+    /// Local template directory layered over the embedded templates.
+    /// A relative path is relative to the directory of the config file.
+    pub template_dir: Option<PathBuf>,
+}
+
+/// Named set of answers that skip the matching prompts.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Preset {
+    pub package_manager: Option<PackageManager>,
+    pub linter: Option<Linter>,
+    pub formatter: Option<Formatter>,
+    pub test_tool: Option<TestTool>,
+}
+
+impl UserConfig {
+    /// Find a preset by name.
+    ///
+    /// ### Parameters
+    /// - `name` - Name of the preset
+    ///
+    /// ### Returns
+    /// - `&Preset` - Preset
+    pub fn preset(&self, name: &str) -> Result<&Preset> {
+        self.presets.get(name).ok_or_else(|| {
+            let available = self.presets.keys().cloned().collect::<Vec<_>>();
+            if available.is_empty() {
+                anyhow::anyhow!(
+                    "Preset '{}' not found: no presets are defined in {}",
+                    name,
+                    display_user_config_path()
+                )
+            } else {
+                anyhow::anyhow!(
+                    "Preset '{}' not found. Available presets: {}",
+                    name,
+                    available.join(", ")
+                )
+            }
+        })
+    }
+}
+
+/// Load the user configuration.
+///
+/// A missing file is treated as an empty configuration.
+///
+/// ### Returns
+/// - `UserConfig` - UserConfig
+pub fn load_user_config() -> Result<UserConfig> {
+    let Some(path) = user_config_path() else {
+        return Ok(UserConfig::default());
+    };
+    if !path.exists() {
+        return Ok(UserConfig::default());
+    }
+
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let mut config: UserConfig =
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))?;
+
+    if let Some(template_dir) = config.template_dir.take() {
+        config.template_dir = Some(resolve_config_path(&template_dir, &path));
+    }
+
+    Ok(config)
+}
+
+/// Path of the user configuration file.
+///
+/// `$XDG_CONFIG_HOME/ts-cdk/config.toml` when `XDG_CONFIG_HOME` is set,
+/// `~/.config/ts-cdk/config.toml` otherwise.
+fn user_config_path() -> Option<PathBuf> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => dirs::home_dir()?.join(".config"),
+    };
+    Some(config_dir.join("ts-cdk").join("config.toml"))
+}
+
+/// Resolve a path written in the config file: `~/` is the home directory, and
+/// relative paths are relative to the directory of the config file.
+fn resolve_config_path(path: &Path, config_path: &Path) -> PathBuf {
+    if let (Ok(rest), Some(home_dir)) = (path.strip_prefix("~"), dirs::home_dir()) {
+        return home_dir.join(rest);
+    }
+    match config_path.parent() {
+        Some(config_dir) if path.is_relative() => config_dir.join(path),
+        _ => path.to_path_buf(),
+    }
+}
+
+fn display_user_config_path() -> String {
+    user_config_path()
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "~/.config/ts-cdk/config.toml".to_string())
+}
@@ -3,47 +3,75 @@ extern crate dialoguer;
 extern crate include_dir;
 
 use anyhow::Result;
-use std::env;
+use clap::Parser;
 
 mod cli;
 mod templates;
 
-const AVAILABLE_COMMANDS: [&str; 2] = ["init", "help"];
-const USAGE: &str = "Usage: ts-cdk <command> [arguments]
+use cli::template::TemplateCommand;
+use cli::{Cli, Command};
 
-Commands:
-    init <directory>    Create a new TypeScript + AWS CDK project in the specified directory
-    help                Show this help message
+fn main() -> Result<()> {
+    let cli = Cli::parse();
 
-Example:
-    `ts-cdk init my-project`    Create a new project in 'my-project' directory";
+    match cli.command {
+        Command::Init(args) => {
+            let user_config = cli::user_config::load_user_config()?;
+            let sources = cli::init::template_sources(&args)?;
+            let loader = cli::init::template_loader(&sources, &user_config)?;
+            let config =
+                cli::init::generate_project_config(&args, &user_config, loader.manifest())?;
+            let mut files = templates::generate::generate_template_files(&config, &loader)?;
+            templates::validate::validate_template_files(&files)?;
+            if args.frozen_lockfile {
+                cli::init::check_lockfile(&config, &files)?;
+            }
+            files.push(templates::manifest::generate_manifest(
+                &config,
+                &sources.relative_to(&config.target_dir_path)?,
+                &files,
+            )?);
+            let hooks = if args.skip_hooks {
+                Vec::new()
+            } else {
+                cli::hooks::plan_hooks(&config, loader.manifest())?
+            };
 
-fn main() -> Result<()> {
-    let args: Vec<String> = env::args().collect();
-    
-    if args.len() < 2 {
-        println!("{}", USAGE);
-        anyhow::bail!("No command provided");
-    }
-    let command = &args[1];
+            if args.dry_run {
+                cli::dry_run::print_plan(&config, &files, &hooks, args.show_contents);
+                if let Some(path) = &args.save_answers {
+                    println!("Would save answers to {}", path.display());
+                }
+                return Ok(());
+            }
+            if let Some(path) = &args.save_answers {
+                cli::answers::save_answers(&config, path)?;
+            }
 
-    match command.as_str() {
-        "init" => {
-            if args.len() < 3 {
-                println!("{}", USAGE);
-                anyhow::bail!("Usage: ts-cdk init <directory>");
+            let files = cli::conflict::resolve_conflicts(
+                &config,
+                files,
+                args.conflict_strategy(),
+                args.is_interactive(),
+            )?;
+            let project = templates::write::write_template_files(&config, &files)?;
+            if args.skip_install {
+                println!(
+                    "Skipped installing dependencies. Run `{} install` in {} to install them.",
+                    config.package_manager.command(),
+                    config.target_dir_path.display()
+                );
+            } else if let Err(e) = cli::init::install_dependencies(&config, args.frozen_lockfile) {
+                cli::init::offer_cleanup(&project, args.is_interactive())?;
+                return Err(e);
             }
-            let target_dir = &args[2];
-            let config = cli::init::generate_project_config(&vec![target_dir.to_string()])?;
-            templates::generate::generate_template_files(&config)?;
-            cli::init::install_dependencies(&config)?;
+            cli::hooks::run_hooks(&config, &hooks, args.yes, args.is_interactive())?;
         }
-        "help" => {
-            println!("{}", USAGE);
+        Command::Add(args) => {
+            cli::add::add(&args)?;
         }
-        _ => {
-            println!("{}", USAGE);
-            anyhow::bail!("Invalid command '{}'. Available commands: {}", command, AVAILABLE_COMMANDS.join(", "));
+        Command::Template(TemplateCommand::Check(args)) => {
+            cli::template::check_templates(&args)?;
         }
     }
 
@@ -0,0 +1,164 @@
+use crate::templates::manifest::checksum;
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Template pack in a git repository, e.g. `git+https://example.com/starters.git#v2`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GitTemplate {
+    /// URL passed to `git clone`
+    pub url: String,
+    /// Branch, tag or commit; the default branch when not given
+    pub reference: Option<String>,
+}
+
+impl GitTemplate {
+    /// Parse a template pack location.
+    ///
+    /// The `git+` prefix is optional and the reference follows `#`.
+    ///
+    /// ### Parameters
+    /// - `spec` - Location such as `git+file:///path/to/repo.git#v2`
+    ///
+    /// ### Returns
+    /// - `GitTemplate` - GitTemplate
+    pub fn parse(spec: &str) -> Result<Self> {
+        let spec = spec.strip_prefix("git+").unwrap_or(spec);
+        let (url, reference) = match spec.rsplit_once('#') {
+            Some((url, reference)) if !reference.is_empty() => (url, Some(reference.to_string())),
+            Some((url, _)) => (url, None),
+            None => (spec, None),
+        };
+        if url.is_empty() {
+            anyhow::bail!("Template '{}' does not contain a repository URL", spec);
+        }
+        Ok(GitTemplate {
+            url: url.to_string(),
+            reference,
+        })
+    }
+
+    /// Clone or update the template pack in the local cache and check out its reference.
+    ///
+    /// When the repository cannot be reached, the cached copy is used, so repeated
+    /// runs work offline.
+    ///
+    /// ### Returns
+    /// - `PathBuf` - Directory holding the checked out template pack
+    pub fn fetch(&self) -> Result<PathBuf> {
+        which::which("git").map_err(|_| {
+            anyhow::anyhow!("'git' was not found on PATH; it is needed for --template")
+        })?;
+
+        let cache_dir = template_cache_dir()?.join(&checksum(&self.url)[..16]);
+        if cache_dir.join(".git").is_dir() {
+            if let Err(e) = git(
+                &cache_dir,
+                &["fetch", "--quiet", "--tags", "--force", "origin"],
+            ) {
+                eprintln!(
+                    "Warning: could not update template '{}', using the cached copy ({:#})",
+                    self.url, e
+                );
+            }
+        } else {
+            if let Some(parent) = cache_dir.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let _ = std::fs::remove_dir_all(&cache_dir);
+            let cache_dir_arg = cache_dir.to_string_lossy();
+            git(
+                Path::new("."),
+                &[
+                    "clone",
+                    "--quiet",
+                    "--no-checkout",
+                    // NOTE: a URL starting with `-` must not be read as an option.
+                    "--",
+                    &self.url,
+                    &cache_dir_arg,
+                ],
+            )
+            .with_context(|| format!("Failed to clone template '{}'", self.url))?;
+        }
+
+        let commit = self.resolve_commit(&cache_dir)?;
+        git(
+            &cache_dir,
+            &["checkout", "--quiet", "--force", "--detach", &commit],
+        )?;
+
+        Ok(cache_dir)
+    }
+
+    /// Resolve the reference to a commit, preferring the remote branch over a stale local one.
+    fn resolve_commit(&self, repo_dir: &Path) -> Result<String> {
+        let candidates = match &self.reference {
+            Some(reference) => vec![format!("origin/{}", reference), reference.clone()],
+            None => vec!["origin/HEAD".to_string()],
+        };
+        for candidate in &candidates {
+            let revision = format!("{}^{{commit}}", candidate);
+            if let Ok(commit) = git(repo_dir, &["rev-parse", "--verify", "--quiet", &revision]) {
+                return Ok(commit.trim().to_string());
+            }
+        }
+        anyhow::bail!(
+            "Reference '{}' was not found in template '{}'",
+            self.reference.as_deref().unwrap_or("HEAD"),
+            self.url
+        )
+    }
+}
+
+/// Directory where template packs are cached (`~/.cache/ts-cdk/templates` on Linux).
+fn template_cache_dir() -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .ok_or_else(|| anyhow::anyhow!("Cannot determine the cache directory for templates"))?;
+    Ok(cache_dir.join("ts-cdk").join("templates"))
+}
+
+/// Run a git command and return its standard output.
+fn git(current_dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(current_dir)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_template_specs() {
+        let template = |url: &str, reference: Option<&str>| GitTemplate {
+            url: url.to_string(),
+            reference: reference.map(str::to_string),
+        };
+        assert_eq!(
+            GitTemplate::parse("git+https://example.com/starters.git#v2").unwrap(),
+            template("https://example.com/starters.git", Some("v2"))
+        );
+        assert_eq!(
+            GitTemplate::parse("https://example.com/starters.git").unwrap(),
+            template("https://example.com/starters.git", None)
+        );
+        assert_eq!(
+            GitTemplate::parse("git+file:///tmp/starters#").unwrap(),
+            template("file:///tmp/starters", None)
+        );
+        assert!(GitTemplate::parse("git+#v2").is_err());
+        assert!(GitTemplate::parse("").is_err());
+    }
+}
@@ -0,0 +1,138 @@
+use crate::templates::assets::TEMPLATES;
+use crate::templates::template_manifest::{TemplateManifest, TEMPLATE_MANIFEST_FILE_NAME};
+
+use anyhow::{Context, Result};
+use include_dir::{Dir, DirEntry};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+/// Loads templates from local directories layered over the embedded `TEMPLATES`.
+///
+/// Lookup is file by file: the first directory holding a template wins, and any
+/// template not found in a directory comes from the embedded templates.
+/// Only the `template.toml` of the highest priority directory holding one applies.
+#[derive(Debug, Default)]
+pub struct TemplateLoader {
+    /// Local template directories, highest priority first
+    layers: Vec<PathBuf>,
+    manifest: TemplateManifest,
+}
+
+impl TemplateLoader {
+    /// Create a loader.
+    ///
+    /// ### Parameters
+    /// - `layers` - Local template directories, highest priority first
+    ///
+    /// ### Returns
+    /// - `TemplateLoader` - TemplateLoader
+    pub fn new(layers: Vec<PathBuf>) -> Result<Self> {
+        for layer in &layers {
+            if !layer.is_dir() {
+                anyhow::bail!("Template directory '{}' does not exist", layer.display());
+            }
+        }
+
+        let manifest = match layers
+            .iter()
+            .map(|layer| layer.join(TEMPLATE_MANIFEST_FILE_NAME))
+            .find(|path| path.is_file())
+        {
+            Some(path) => {
+                let content = std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                TemplateManifest::parse(&content)
+                    .with_context(|| format!("Invalid template manifest {}", path.display()))?
+            }
+            None => TemplateManifest::default(),
+        };
+
+        Ok(TemplateLoader { layers, manifest })
+    }
+
+    /// Manifest of the template pack, empty when there is none.
+    pub fn manifest(&self) -> &TemplateManifest {
+        &self.manifest
+    }
+
+    /// Whether a local template directory holds the template.
+    pub fn is_overridden(&self, template_path: &str) -> bool {
+        self.layers
+            .iter()
+            .any(|layer| layer.join(template_path).is_file())
+    }
+
+    /// Whether a template exists in a local template directory or the embedded templates.
+    pub fn exists(&self, template_path: &str) -> bool {
+        self.is_overridden(template_path) || TEMPLATES.get_file(template_path).is_some()
+    }
+
+    /// Paths of the files in the local template directories, or of the embedded templates
+    /// when there are none.
+    ///
+    /// ### Returns
+    /// - `BTreeSet<String>` - Paths relative to the template directory, separated by `/`
+    pub fn template_paths(&self) -> Result<BTreeSet<String>> {
+        let mut paths = BTreeSet::new();
+        if self.layers.is_empty() {
+            collect_embedded_paths(&TEMPLATES, &mut paths);
+        }
+        for layer in &self.layers {
+            collect_local_paths(layer, layer, &mut paths)?;
+        }
+        Ok(paths)
+    }
+
+    /// Load a template.
+    ///
+    /// ### Parameters
+    /// - `template_path` - Path of the template relative to the template directory
+    ///
+    /// ### Returns
+    /// - `String` - Content of the template
+    pub fn load(&self, template_path: &str) -> Result<String> {
+        for layer in &self.layers {
+            let path = layer.join(template_path);
+            if path.is_file() {
+                return std::fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {} template", path.display()));
+            }
+        }
+
+        let content = TEMPLATES
+            .get_file(template_path)
+            .ok_or_else(|| anyhow::anyhow!("Failed to load {} template", template_path))?
+            .contents_utf8()
+            .ok_or_else(|| anyhow::anyhow!("Failed to read {} template as UTF-8", template_path))?;
+        Ok(content.to_string())
+    }
+}
+
+fn collect_embedded_paths(dir: &Dir, paths: &mut BTreeSet<String>) {
+    for entry in dir.entries() {
+        match entry {
+            DirEntry::Dir(dir) => collect_embedded_paths(dir, paths),
+            DirEntry::File(file) => {
+                paths.insert(file.path().to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+}
+
+fn collect_local_paths(root: &Path, dir: &Path, paths: &mut BTreeSet<String>) -> Result<()> {
+    for entry in std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read template directory {}", dir.display()))?
+    {
+        let path = entry?.path();
+        if path.is_dir() {
+            // NOTE: template packs cloned from git hold their repository.
+            if path.file_name().is_some_and(|name| name == ".git") {
+                continue;
+            }
+            collect_local_paths(root, &path, paths)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            paths.insert(relative.to_string_lossy().replace('\\', "/"));
+        }
+    }
+    Ok(())
+}
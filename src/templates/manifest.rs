@@ -0,0 +1,220 @@
+use crate::cli::init::ProjectConfig;
+use crate::templates::generate::TemplateFile;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::{Component, Path, PathBuf};
+
+// NOTE: Written into the root of every generated project.
+pub const MANIFEST_FILE_NAME: &str = ".ts-cdk.toml";
+
+/// Record of how a project was generated.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Version of ts-cdk that generated the project
+    pub version: String,
+    /// Choices the project was created with
+    pub project: ProjectConfig,
+    /// SHA-256 checksum of every generated file, keyed by its path in the project
+    pub files: BTreeMap<String, String>,
+    /// Templates the project was generated from, reused by the `add` commands
+    #[serde(default, skip_serializing_if = "TemplateSources::is_embedded")]
+    pub templates: TemplateSources,
+}
+
+/// Templates given to `init` besides the embedded ones.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TemplateSources {
+    /// `--template-dir`, relative to the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template_dir: Option<PathBuf>,
+    /// `--template`, e.g. `git+https://example.com/starters.git#v2`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+}
+
+impl TemplateSources {
+    /// Whether only the embedded templates (and the user configuration's) are used.
+    pub fn is_embedded(&self) -> bool {
+        self.template_dir.is_none() && self.template.is_none()
+    }
+
+    /// The sources as recorded in `.ts-cdk.toml`: the template directory relative to the
+    /// project, so that the record holds in every checkout.
+    ///
+    /// ### Parameters
+    /// - `project_dir` - Root directory of the project, which may not exist yet
+    ///
+    /// ### Returns
+    /// - `TemplateSources` - Sources with a relative `template_dir`
+    pub fn relative_to(&self, project_dir: &Path) -> Result<Self> {
+        let template_dir = match &self.template_dir {
+            Some(dir) => Some(relative_path(
+                &absolute_path(project_dir)?,
+                &absolute_path(dir)?,
+            )),
+            None => None,
+        };
+        Ok(TemplateSources {
+            template_dir,
+            template: self.template.clone(),
+        })
+    }
+}
+
+/// Generate the manifest of the project.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `templates` - Templates given to `init`
+/// - `files` - Generated files
+///
+/// ### Returns
+/// - `TemplateFile` - Manifest file
+pub fn generate_manifest(
+    config: &ProjectConfig,
+    templates: &TemplateSources,
+    files: &[TemplateFile],
+) -> Result<TemplateFile> {
+    let manifest = Manifest {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        project: config.clone(),
+        files: files
+            .iter()
+            .map(|file| (file.file_path.clone(), checksum(&file.content)))
+            .collect(),
+        templates: templates.clone(),
+    };
+
+    manifest.to_template_file()
+}
+
+impl Manifest {
+    /// Load the manifest of a generated project.
+    ///
+    /// ### Parameters
+    /// - `project_dir` - Root directory of the project
+    ///
+    /// ### Returns
+    /// - `Manifest` - Manifest, its `project.target_dir_path` set to `project_dir`
+    pub fn load(project_dir: &Path) -> Result<Self> {
+        let path = project_dir.join(MANIFEST_FILE_NAME);
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let mut manifest: Manifest = toml::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", path.display()))?;
+        manifest.project.target_dir_path = project_dir.to_path_buf();
+        Ok(manifest)
+    }
+
+    /// Record the checksum of a file written into the project.
+    ///
+    /// A file changed by the user since ts-cdk wrote it keeps its old checksum,
+    /// so it is still seen as modified.
+    ///
+    /// ### Parameters
+    /// - `file_path` - Path of the file relative to the project root
+    /// - `previous` - Content of the file before it was written, `None` for a new file
+    /// - `content` - Written content
+    pub fn record(&mut self, file_path: &str, previous: Option<&str>, content: &str) {
+        let unmodified = match previous {
+            Some(previous) => self.is_unmodified(file_path, previous),
+            None => true,
+        };
+        if unmodified {
+            self.files.insert(file_path.to_string(), checksum(content));
+        }
+    }
+
+    /// Whether a file still has the content ts-cdk wrote.
+    pub fn is_unmodified(&self, file_path: &str, content: &str) -> bool {
+        self.files.get(file_path) == Some(&checksum(content))
+    }
+
+    /// The manifest as a file to write into the project.
+    pub fn to_template_file(&self) -> Result<TemplateFile> {
+        Ok(TemplateFile {
+            file_path: MANIFEST_FILE_NAME.to_string(),
+            content: toml::to_string(self)?,
+            template: None,
+        })
+    }
+}
+
+/// SHA-256 checksum of a file content as a lowercase hex string.
+pub fn checksum(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Absolute path of a directory that may not exist yet, with symlinks of its existing
+/// ancestors resolved like `std::fs::canonicalize` does.
+fn absolute_path(path: &Path) -> Result<PathBuf> {
+    let path = std::env::current_dir()?.join(path);
+    let existing = path
+        .ancestors()
+        .find(|dir| dir.exists())
+        .ok_or_else(|| anyhow::anyhow!("Cannot resolve '{}'", path.display()))?;
+    let rest = path.strip_prefix(existing)?;
+    Ok(std::fs::canonicalize(existing)?.join(rest))
+}
+
+/// Path of `to` relative to the directory `from`; both must be absolute.
+///
+/// ### Returns
+/// - `PathBuf` - Relative path, `to` itself when they share no root (e.g. another drive)
+fn relative_path(from: &Path, to: &Path) -> PathBuf {
+    let from = from
+        .components()
+        .filter(|component| *component != Component::CurDir)
+        .collect::<Vec<_>>();
+    let to_components = to.components().collect::<Vec<_>>();
+    let common = from
+        .iter()
+        .zip(&to_components)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return to.to_path_buf();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in common..from.len() {
+        relative.push("..");
+    }
+    for component in &to_components[common..] {
+        relative.push(component.as_os_str());
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn makes_paths_relative() {
+        let cases = [
+            ("/work/app", "/work/templates", "../templates"),
+            ("/work/app", "/work/app/templates", "templates"),
+            ("/work/app/.", "/shared/templates", "../../shared/templates"),
+            ("/work/app", "/work/app", "."),
+        ];
+        for (from, to, expected) in cases {
+            assert_eq!(
+                relative_path(Path::new(from), Path::new(to)),
+                PathBuf::from(expected),
+                "{} -> {}",
+                from,
+                to
+            );
+        }
+    }
+}
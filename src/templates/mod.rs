@@ -1,2 +1,12 @@
+pub mod assets;
 pub mod generate;
-pub mod assets;
\ No newline at end of file
+pub mod git;
+pub mod loader;
+pub mod manifest;
+pub mod naming;
+pub mod package_json;
+pub mod registry;
+pub mod render;
+pub mod template_manifest;
+pub mod validate;
+pub mod write;
@@ -0,0 +1,235 @@
+use serde::Serialize;
+
+/// Case variants of a name, used for file names, class names and stack ids.
+///
+/// Every variant is built from the same list of words, so converting any variant
+/// back with `Naming::new` gives the same `Naming`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Naming {
+    /// `my-api-service`
+    pub kebab: String,
+    /// `MyApiService`
+    pub pascal: String,
+    /// `myApiService`
+    pub camel: String,
+    /// `my_api_service`
+    pub snake: String,
+    /// `MY_API_SERVICE`
+    pub constant: String,
+}
+
+impl Naming {
+    /// Build every case variant of a name.
+    ///
+    /// ### Parameters
+    /// - `name` - Name in any case style, e.g. `MyAPIService`, `my_api service`
+    ///
+    /// ### Returns
+    /// - `Naming` - Naming
+    pub fn new(name: &str) -> Self {
+        let words = split_words(name);
+
+        let pascal = words
+            .iter()
+            .map(|word| capitalize(word))
+            .collect::<String>();
+        let camel = words
+            .iter()
+            .enumerate()
+            .map(|(index, word)| {
+                if index == 0 {
+                    word.clone()
+                } else {
+                    capitalize(word)
+                }
+            })
+            .collect::<String>();
+
+        Naming {
+            kebab: words.join("-"),
+            pascal,
+            camel,
+            snake: words.join("_"),
+            constant: words.join("_").to_uppercase(),
+        }
+    }
+}
+
+/// Split a name into lowercase words.
+///
+/// Words are separated by any character that is not alphanumeric, by a lowercase letter
+/// or digit followed by an uppercase letter (`myApp`, `s3Bucket`), and before the last
+/// letter of an acronym that is followed by a lowercase letter (`APIService`).
+/// Digits stay attached to the preceding letters (`ec2`, `v2`).
+fn split_words(name: &str) -> Vec<String> {
+    let chars = name.chars().collect::<Vec<char>>();
+    let mut words = Vec::new();
+    let mut current = String::new();
+
+    for (index, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+
+        if let Some(&previous) = index.checked_sub(1).map(|previous| &chars[previous]) {
+            let next = chars.get(index + 1).copied();
+            let starts_word = c.is_uppercase()
+                && ((previous.is_lowercase() || previous.is_numeric())
+                    || (previous.is_uppercase() && next.is_some_and(char::is_lowercase)));
+            if starts_word && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+        }
+        current.extend(c.to_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+
+    words
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_examples() {
+        let cases = [
+            ("my-app", "my-app", "MyApp"),
+            ("MyApp", "my-app", "MyApp"),
+            ("MyAPIService", "my-api-service", "MyApiService"),
+            ("HTTPServer2", "http-server2", "HttpServer2"),
+            ("s3Bucket", "s3-bucket", "S3Bucket"),
+            ("S3Bucket", "s3-bucket", "S3Bucket"),
+            ("ec2_instance v2", "ec2-instance-v2", "Ec2InstanceV2"),
+            ("my--app__name", "my-app-name", "MyAppName"),
+            (
+                "  leading and trailing  ",
+                "leading-and-trailing",
+                "LeadingAndTrailing",
+            ),
+            ("über-cool", "über-cool", "ÜberCool"),
+            ("データ-stack", "データ-stack", "データStack"),
+        ];
+        for (name, kebab, pascal) in cases {
+            let naming = Naming::new(name);
+            assert_eq!(naming.kebab, kebab, "kebab of {:?}", name);
+            assert_eq!(naming.pascal, pascal, "pascal of {:?}", name);
+        }
+    }
+
+    #[test]
+    fn builds_every_variant() {
+        let naming = Naming::new("MyAPIService");
+        assert_eq!(
+            naming,
+            Naming {
+                kebab: "my-api-service".to_string(),
+                pascal: "MyApiService".to_string(),
+                camel: "myApiService".to_string(),
+                snake: "my_api_service".to_string(),
+                constant: "MY_API_SERVICE".to_string(),
+            }
+        );
+    }
+
+    /// Names built from every combination of words, separators and case styles.
+    ///
+    /// Words have at least two letters and do not start with a digit: otherwise they
+    /// cannot be told apart once written in PascalCase (`a-b` becomes `AB`, `my-2nd` becomes `My2nd`).
+    fn generated_names() -> Vec<String> {
+        let words = [
+            "my", "api", "service", "s3", "ec2", "v2", "über", "http", "app",
+        ];
+        let mut names = Vec::new();
+        for first in words {
+            for second in words {
+                for third in ["", "stack", "db"] {
+                    let parts = [first, second, third]
+                        .into_iter()
+                        .filter(|part| !part.is_empty())
+                        .collect::<Vec<_>>();
+                    names.push(parts.join("-"));
+                    names.push(parts.join("_"));
+                    names.push(parts.join(" "));
+                    names.push(parts.join("."));
+                    names.push(parts.iter().map(|part| capitalize(part)).collect());
+                    names.push(parts.join("_").to_uppercase());
+                }
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn every_variant_converts_back_to_the_same_naming() {
+        for name in generated_names() {
+            let naming = Naming::new(&name);
+            for variant in [
+                &naming.kebab,
+                &naming.pascal,
+                &naming.camel,
+                &naming.snake,
+                &naming.constant,
+            ] {
+                assert_eq!(
+                    Naming::new(variant),
+                    naming,
+                    "{:?} -> {:?} does not convert back",
+                    name,
+                    variant
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn file_and_class_names_stay_consistent() {
+        for name in generated_names() {
+            let naming = Naming::new(&name);
+            let pascal_from_kebab = naming.kebab.split('-').map(capitalize).collect::<String>();
+            assert_eq!(naming.pascal, pascal_from_kebab, "{:?}", name);
+            assert_eq!(naming.snake, naming.kebab.replace('-', "_"), "{:?}", name);
+            assert_eq!(
+                naming.pascal.to_lowercase(),
+                naming.camel.to_lowercase(),
+                "{:?}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn kebab_case_is_well_formed() {
+        for name in generated_names() {
+            let kebab = Naming::new(&name).kebab;
+            assert!(!kebab.is_empty(), "{:?}", name);
+            assert!(
+                !kebab.starts_with('-') && !kebab.ends_with('-'),
+                "{:?}",
+                name
+            );
+            assert!(!kebab.contains("--"), "{:?}", name);
+            assert!(
+                kebab
+                    .chars()
+                    .all(|c| c == '-' || c.is_numeric() || c.is_lowercase()),
+                "{:?} -> {:?}",
+                name,
+                kebab
+            );
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use crate::cli::init::{Formatter, Linter, TestTool};
+use crate::templates::render::TemplateContext;
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// Typed model of `package.json`.
+///
+/// Maps are `BTreeMap`s so the output has a deterministic key order.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PackageJson {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub version: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub scripts: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dev_dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dependencies: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub engines: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_manager: Option<String>,
+}
+
+/// `package.json` of an existing project, edited in place so that its fields keep their order.
+#[derive(Debug)]
+pub struct PackageJsonDocument {
+    fields: serde_json::Map<String, Value>,
+}
+
+/// Entries a tool adds to `package.json`.
+pub trait PackageJsonContribution {
+    fn contribute(&self, package_json: &mut PackageJson);
+}
+
+impl PackageJson {
+    /// Build the `package.json` of a new project.
+    ///
+    /// ### Parameters
+    /// - `context` - TemplateContext
+    ///
+    /// ### Returns
+    /// - `PackageJson` - PackageJson
+    pub fn new(context: &TemplateContext) -> Self {
+        let mut package_json = PackageJson {
+            name: context.package_name.clone(),
+            version: "0.1.0".to_string(),
+            ..Default::default()
+        };
+        package_json.add_script("type-check", "tsc --noEmit");
+        package_json.add_script("cdk", "cdk");
+        package_json.add_dev_dependency("@types/node", "22.10.1");
+        package_json.add_dev_dependency("aws-cdk", "2.166.0");
+        package_json.add_dev_dependency("ts-node", "^10.9.2");
+        package_json.add_dev_dependency("typescript", "~5.6.3");
+        package_json.add_dependency("aws-cdk-lib", "^2.171.1");
+        package_json.add_dependency("constructs", "^10.4.2");
+        package_json.add_dependency("source-map-support", "^0.5.21");
+        package_json
+            .engines
+            .insert("node".to_string(), ">=18.0.0".to_string());
+
+        // NOTE: pinned only on request, so that generating files never runs the package manager.
+        package_json.package_manager = context
+            .package_manager_version
+            .as_ref()
+            .map(|version| format!("{}@{}", context.package_manager.command(), version));
+        context.linter.contribute(&mut package_json);
+        context.formatter.contribute(&mut package_json);
+        context.test_tool.contribute(&mut package_json);
+
+        package_json
+    }
+
+    /// Add or replace a script.
+    pub fn add_script(&mut self, name: &str, command: &str) {
+        self.scripts.insert(name.to_string(), command.to_string());
+    }
+
+    /// Add or replace a devDependency.
+    pub fn add_dev_dependency(&mut self, name: &str, version: &str) {
+        self.dev_dependencies
+            .insert(name.to_string(), version.to_string());
+    }
+
+    /// Add or replace a dependency.
+    pub fn add_dependency(&mut self, name: &str, version: &str) {
+        self.dependencies
+            .insert(name.to_string(), version.to_string());
+    }
+
+    /// Serialize as pretty-printed JSON with a trailing newline.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)? + "\n")
+    }
+}
+
+impl PackageJsonDocument {
+    /// Parse an existing `package.json`.
+    pub fn parse(content: &str) -> Result<Self> {
+        match serde_json::from_str(content)? {
+            Value::Object(fields) => Ok(PackageJsonDocument { fields }),
+            _ => anyhow::bail!("package.json is not a JSON object"),
+        }
+    }
+
+    /// Version range of a dependency, `None` when the project does not depend on it.
+    pub fn dependency(&self, name: &str) -> Option<&str> {
+        self.fields.get("dependencies")?.get(name)?.as_str()
+    }
+
+    /// Add or replace a dependency.
+    pub fn add_dependency(&mut self, name: &str, version: &str) -> Result<()> {
+        self.insert("dependencies", name, version)
+    }
+
+    /// Add or replace a devDependency.
+    pub fn add_dev_dependency(&mut self, name: &str, version: &str) -> Result<()> {
+        self.insert("devDependencies", name, version)
+    }
+
+    /// Serialize as pretty-printed JSON with a trailing newline.
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(&self.fields)? + "\n")
+    }
+
+    /// Set an entry of a dependency section, keeping the section sorted when it was.
+    fn insert(&mut self, section: &str, name: &str, version: &str) -> Result<()> {
+        let entries = self
+            .fields
+            .entry(section)
+            .or_insert_with(|| Value::Object(serde_json::Map::new()))
+            .as_object_mut()
+            .ok_or_else(|| anyhow::anyhow!("`{}` of package.json is not an object", section))?;
+        // NOTE: npm keeps dependencies sorted; a section sorted by hand is left alone.
+        let sorted = entries
+            .keys()
+            .zip(entries.keys().skip(1))
+            .all(|(a, b)| a <= b);
+        entries.insert(name.to_string(), Value::String(version.to_string()));
+        if sorted {
+            entries.sort_keys();
+        }
+        Ok(())
+    }
+}
+
+impl PackageJsonContribution for Linter {
+    fn contribute(&self, package_json: &mut PackageJson) {
+        match self {
+            Linter::EsLint => {
+                package_json.add_script("lint", "eslint --config eslint.config.mjs");
+                package_json.add_dev_dependency("@eslint/js", "^9.19.0");
+                package_json.add_dev_dependency("typescript-eslint", "^8.14.0");
+                package_json.add_dev_dependency("eslint-cdk-plugin", "^1.1.1");
+            }
+            Linter::Biome => {
+                package_json.add_script("lint", "biome lint");
+                package_json.add_dev_dependency("@biomejs/biome", "^1.9.4");
+            }
+            Linter::None => {}
+        }
+    }
+}
+
+impl PackageJsonContribution for Formatter {
+    fn contribute(&self, package_json: &mut PackageJson) {
+        match self {
+            Formatter::Prettier => {
+                package_json.add_script(
+                    "format",
+                    "prettier --write '**/*.ts' --ignore-path .prettierignore",
+                );
+                package_json.add_dev_dependency("prettier", "^3.4.2");
+            }
+            Formatter::Biome => {
+                package_json.add_script("format", "biome format");
+                package_json.add_dev_dependency("@biomejs/biome", "^1.9.4");
+            }
+            Formatter::None => {}
+        }
+    }
+}
+
+impl PackageJsonContribution for TestTool {
+    fn contribute(&self, package_json: &mut PackageJson) {
+        match self {
+            TestTool::Jest => {
+                package_json.add_script("test", "jest");
+                package_json.add_dev_dependency("@types/jest", "^29.5.14");
+                package_json.add_dev_dependency("jest", "^29.7.0");
+                package_json.add_dev_dependency("ts-jest", "^29.2.5");
+            }
+            TestTool::Vitest => {
+                package_json.add_script("test", "vitest --run");
+                package_json.add_dev_dependency("vitest", "^3.0.4");
+            }
+            TestTool::None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::{PackageManager, ProjectConfig};
+    use std::path::PathBuf;
+
+    fn package_json(
+        linter: Linter,
+        formatter: Formatter,
+        test_tool: TestTool,
+        package_manager_version: Option<&str>,
+    ) -> PackageJson {
+        PackageJson::new(&TemplateContext::new(&ProjectConfig {
+            target_dir_path: PathBuf::from("."),
+            name: "my-app".to_string(),
+            package_manager: PackageManager::Pnpm,
+            linter,
+            formatter,
+            test_tool,
+            package_manager_version: package_manager_version.map(str::to_string),
+            variables: BTreeMap::new(),
+        }))
+    }
+
+    #[test]
+    fn contributes_entries_per_tool() {
+        let base = package_json(Linter::None, Formatter::None, TestTool::None, None);
+        assert_eq!(
+            base.scripts.keys().collect::<Vec<_>>(),
+            vec!["cdk", "type-check"]
+        );
+        assert_eq!(base.package_manager, None);
+
+        let cases = [
+            (
+                Linter::EsLint,
+                Formatter::None,
+                TestTool::None,
+                "lint",
+                "eslint-cdk-plugin",
+            ),
+            (
+                Linter::Biome,
+                Formatter::None,
+                TestTool::None,
+                "lint",
+                "@biomejs/biome",
+            ),
+            (
+                Linter::None,
+                Formatter::Prettier,
+                TestTool::None,
+                "format",
+                "prettier",
+            ),
+            (
+                Linter::None,
+                Formatter::Biome,
+                TestTool::None,
+                "format",
+                "@biomejs/biome",
+            ),
+            (
+                Linter::None,
+                Formatter::None,
+                TestTool::Jest,
+                "test",
+                "ts-jest",
+            ),
+            (
+                Linter::None,
+                Formatter::None,
+                TestTool::Vitest,
+                "test",
+                "vitest",
+            ),
+        ];
+        for (linter, formatter, test_tool, script, dev_dependency) in cases {
+            let package_json = package_json(linter, formatter, test_tool, None);
+            assert_eq!(package_json.scripts.len(), base.scripts.len() + 1);
+            assert!(package_json.scripts.contains_key(script), "{}", script);
+            assert!(
+                package_json.dev_dependencies.contains_key(dev_dependency),
+                "{}",
+                dev_dependency
+            );
+            assert_eq!(package_json.dependencies, base.dependencies);
+        }
+
+        let pinned = package_json(
+            Linter::None,
+            Formatter::None,
+            TestTool::None,
+            Some("9.15.4"),
+        );
+        assert_eq!(pinned.package_manager.as_deref(), Some("pnpm@9.15.4"));
+    }
+
+    #[test]
+    fn serializes_in_a_fixed_order() {
+        let json = package_json(
+            Linter::Biome,
+            Formatter::Biome,
+            TestTool::Vitest,
+            Some("9.15.4"),
+        )
+        .to_json()
+        .unwrap();
+        assert!(json.ends_with("}\n") && !json.ends_with("\n\n"));
+        let value = serde_json::from_str::<serde_json::Map<String, Value>>(&json).unwrap();
+        assert_eq!(
+            value.keys().collect::<Vec<_>>(),
+            vec![
+                "name",
+                "version",
+                "scripts",
+                "devDependencies",
+                "dependencies",
+                "engines",
+                "packageManager"
+            ]
+        );
+        assert_eq!(
+            value["scripts"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["cdk", "format", "lint", "test", "type-check"]
+        );
+        // NOTE: Biome is both the linter and the formatter, and is only listed once.
+        assert_eq!(
+            value["devDependencies"]
+                .as_object()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            vec![
+                "@biomejs/biome",
+                "@types/node",
+                "aws-cdk",
+                "ts-node",
+                "typescript",
+                "vitest"
+            ]
+        );
+    }
+
+    #[test]
+    fn edits_package_json_in_place() {
+        let content = "{\n  \"scripts\": {\n    \"watch\": \"tsc -w\",\n    \"build\": \"tsc\"\n  },\n  \"private\": true,\n  \"dependencies\": {\n    \"aws-cdk-lib\": \"^2.171.1\",\n    \"constructs\": \"^10.4.2\"\n  }\n}\n";
+        let mut package_json = PackageJsonDocument::parse(content).unwrap();
+        assert_eq!(package_json.dependency("aws-cdk-lib"), Some("^2.171.1"));
+        package_json.add_dependency("@aws-cdk/a", "^1.0.0").unwrap();
+        package_json
+            .add_dev_dependency("esbuild", "^0.24.2")
+            .unwrap();
+        assert_eq!(
+            package_json.to_json().unwrap(),
+            "{\n  \"scripts\": {\n    \"watch\": \"tsc -w\",\n    \"build\": \"tsc\"\n  },\n  \"private\": true,\n  \"dependencies\": {\n    \"@aws-cdk/a\": \"^1.0.0\",\n    \"aws-cdk-lib\": \"^2.171.1\",\n    \"constructs\": \"^10.4.2\"\n  },\n  \"devDependencies\": {\n    \"esbuild\": \"^0.24.2\"\n  }\n}\n"
+        );
+    }
+}
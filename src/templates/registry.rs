@@ -0,0 +1,164 @@
+use crate::cli::init::{Formatter, Linter, TestTool};
+use crate::templates::package_json::PackageJson;
+use crate::templates::render::TemplateContext;
+
+use anyhow::Result;
+
+/// Where the content of a generated file comes from.
+#[derive(Debug)]
+pub enum TemplateSource {
+    /// Template under `templates/`, rendered with the TemplateContext
+    Template(&'static str),
+    /// Content built in Rust
+    Generated(fn(&TemplateContext) -> Result<String>),
+}
+
+/// A file generated by `init`.
+#[derive(Debug)]
+pub struct TemplateEntry {
+    pub source: TemplateSource,
+    /// Path of the file in the project, rendered like a template
+    pub output: &'static str,
+    /// Whether the file is generated for a project
+    pub condition: fn(&TemplateContext) -> bool,
+    /// Variables of the TemplateContext the embedded template may use
+    pub variables: &'static [&'static str],
+}
+
+/// Every file generated by `init`, in output order.
+pub static TEMPLATE_REGISTRY: &[TemplateEntry] = &[
+    TemplateEntry {
+        source: TemplateSource::Generated(generate_package_json),
+        output: "package.json",
+        condition: always,
+        variables: &[
+            "package_name",
+            "package_manager",
+            "linter",
+            "formatter",
+            "test_tool",
+        ],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("tsconfig.json"),
+        output: "tsconfig.json",
+        condition: always,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("README.md"),
+        output: "README.md",
+        condition: always,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("cdk.json"),
+        output: "cdk.json",
+        condition: always,
+        variables: &["name"],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("_.gitignore"),
+        output: ".gitignore",
+        condition: always,
+        variables: &["test_tool"],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template(".npmignore"),
+        output: ".npmignore",
+        condition: always,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("test/%project-name%.test.ts"),
+        output: "test/{{ name.kebab }}.test.ts",
+        condition: always,
+        variables: &["name"],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("lib/%project-name%-stack.ts"),
+        output: "lib/{{ name.kebab }}-stack.ts",
+        condition: always,
+        variables: &["name"],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("bin/%project-name%.ts"),
+        output: "bin/{{ name.kebab }}.ts",
+        condition: always,
+        variables: &["name"],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("eslint.config.mjs"),
+        output: "eslint.config.mjs",
+        condition: |context| context.linter == Linter::EsLint,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("biome.json"),
+        output: "biome.json",
+        condition: |context| {
+            context.linter == Linter::Biome && context.formatter == Formatter::Biome
+        },
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("biome.lint.json"),
+        output: "biome.json",
+        condition: |context| {
+            context.linter == Linter::Biome && context.formatter != Formatter::Biome
+        },
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("vitest.config.mjs"),
+        output: "vitest.config.mjs",
+        condition: |context| context.test_tool == TestTool::Vitest,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("jest.config.js"),
+        output: "jest.config.js",
+        condition: |context| context.test_tool == TestTool::Jest,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template(".prettierrc"),
+        output: ".prettierrc",
+        condition: |context| context.formatter == Formatter::Prettier,
+        variables: &[],
+    },
+    TemplateEntry {
+        source: TemplateSource::Template("biome.format.json"),
+        output: "biome.json",
+        condition: |context| {
+            context.formatter == Formatter::Biome && context.linter != Linter::Biome
+        },
+        variables: &[],
+    },
+];
+
+/// Entry of `TEMPLATE_REGISTRY` rendering a template.
+pub fn registry_entry(template_path: &str) -> Option<&'static TemplateEntry> {
+    TEMPLATE_REGISTRY.iter().find(
+        |entry| matches!(entry.source, TemplateSource::Template(path) if path == template_path),
+    )
+}
+
+/// Paths of the templates used by `TEMPLATE_REGISTRY`.
+pub fn registered_templates() -> impl Iterator<Item = &'static str> {
+    TEMPLATE_REGISTRY
+        .iter()
+        .filter_map(|entry| match entry.source {
+            TemplateSource::Template(template_path) => Some(template_path),
+            TemplateSource::Generated(_) => None,
+        })
+}
+
+fn always(_: &TemplateContext) -> bool {
+    true
+}
+
+fn generate_package_json(context: &TemplateContext) -> Result<String> {
+    // NOTE: built from a typed model rather than a template, so the output is always valid JSON.
+    PackageJson::new(context).to_json()
+}
@@ -0,0 +1,139 @@
+use crate::cli::init::{Formatter, Linter, PackageManager, ProjectConfig, TestTool};
+use crate::cli::project_name::unscoped_name;
+use crate::templates::naming::Naming;
+
+use crate::templates::template_manifest::VariableValue;
+
+use anyhow::Result;
+use minijinja::{Environment, UndefinedBehavior};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Variables available in every template.
+///
+/// ```text
+/// {{ package_name }}          @acme/my-app
+/// {{ name.kebab }}            my-app (also pascal, camel, snake, constant)
+/// {{ package_manager }}       npm | yarn | pnpm
+/// {{ linter }}                eslint | biome | none
+/// {{ formatter }}             prettier | biome | none
+/// {{ test_tool }}             jest | vitest | none
+/// ```
+///
+/// The `kebab`, `pascal`, `camel`, `snake` and `constant` filters convert any string,
+/// e.g. `{{ "MyAPI" | kebab }}`. Answers to the prompts of a `template.toml` are
+/// available under their own names.
+#[derive(Debug, Clone, Serialize)]
+pub struct TemplateContext {
+    pub package_name: String,
+    pub name: Naming,
+    pub package_manager: PackageManager,
+    pub linter: Linter,
+    pub formatter: Formatter,
+    pub test_tool: TestTool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package_manager_version: Option<String>,
+    #[serde(flatten)]
+    pub variables: BTreeMap<String, VariableValue>,
+}
+
+impl TemplateContext {
+    /// Build the template context of a project.
+    ///
+    /// ### Parameters
+    /// - `config` - ProjectConfig
+    ///
+    /// ### Returns
+    /// - `TemplateContext` - TemplateContext
+    pub fn new(config: &ProjectConfig) -> Self {
+        TemplateContext {
+            package_name: config.name.clone(),
+            name: Naming::new(unscoped_name(&config.name)),
+            package_manager: config.package_manager,
+            linter: config.linter,
+            formatter: config.formatter,
+            test_tool: config.test_tool,
+            package_manager_version: config.package_manager_version.clone(),
+            variables: config.variables.clone(),
+        }
+    }
+
+    /// Keep only some of the variables, so a template cannot use the others.
+    ///
+    /// ### Parameters
+    /// - `variables` - Names of the variables to keep
+    ///
+    /// ### Returns
+    /// - `serde_json::Value` - Object holding the selected variables
+    pub fn select(&self, variables: &[&str]) -> Result<serde_json::Value> {
+        let mut selected = serde_json::Map::new();
+        if let serde_json::Value::Object(all) = serde_json::to_value(self)? {
+            for (key, value) in all {
+                if variables.contains(&key.as_str()) {
+                    selected.insert(key, value);
+                }
+            }
+        }
+        Ok(serde_json::Value::Object(selected))
+    }
+}
+
+/// Render a template.
+///
+/// Using a variable that does not exist is an error.
+///
+/// ### Parameters
+/// - `template_path` - Path of the template, used in error messages
+/// - `source` - Content of the template
+/// - `context` - Variables available in the template
+///
+/// ### Returns
+/// - `String` - Rendered content
+pub fn render_template<S: Serialize>(
+    template_path: &str,
+    source: &str,
+    context: &S,
+) -> Result<String> {
+    let mut env = environment();
+    env.add_template(template_path, source)
+        .map_err(|e| anyhow::anyhow!("Failed to parse template {}: {:#}", template_path, e))?;
+    let content = env
+        .get_template(template_path)?
+        .render(context)
+        .map_err(|e| anyhow::anyhow!("Failed to render template {}: {:#}", template_path, e))?;
+
+    Ok(content)
+}
+
+/// Evaluate a MiniJinja expression, e.g. `use_vpc and test_tool != "none"`.
+///
+/// ### Parameters
+/// - `expression` - Expression to evaluate
+/// - `context` - Variables available in the expression
+///
+/// ### Returns
+/// - `bool` - Whether the result is true
+pub fn evaluate_condition<S: Serialize>(expression: &str, context: &S) -> Result<bool> {
+    let env = environment();
+    let value = env
+        .compile_expression(expression)
+        .and_then(|expression| expression.eval(context))
+        .map_err(|e| anyhow::anyhow!("Failed to evaluate '{}': {:#}", expression, e))?;
+    Ok(value.is_true())
+}
+
+/// Environment shared by templates and conditions.
+fn environment() -> Environment<'static> {
+    let mut env = Environment::new();
+    env.set_undefined_behavior(UndefinedBehavior::Strict);
+    env.set_keep_trailing_newline(true);
+    // NOTE: lines holding only a block tag (`{% if ... %}`) do not leave an empty line behind.
+    env.set_trim_blocks(true);
+    env.set_lstrip_blocks(true);
+    env.add_filter("kebab", |value: String| Naming::new(&value).kebab);
+    env.add_filter("pascal", |value: String| Naming::new(&value).pascal);
+    env.add_filter("camel", |value: String| Naming::new(&value).camel);
+    env.add_filter("snake", |value: String| Naming::new(&value).snake);
+    env.add_filter("constant", |value: String| Naming::new(&value).constant);
+    env
+}
@@ -0,0 +1,406 @@
+use crate::templates::render::{evaluate_condition, TemplateContext};
+
+use anyhow::{Context, Result};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Name of the manifest of a template pack, at the root of the template directory.
+pub const TEMPLATE_MANIFEST_FILE_NAME: &str = "template.toml";
+
+/// Variables every template already has; prompts cannot reuse these names.
+const RESERVED_VARIABLES: &[&str] = &[
+    "package_name",
+    "name",
+    "package_manager",
+    "linter",
+    "formatter",
+    "test_tool",
+];
+
+/// Manifest of a template pack (`template.toml`).
+///
+/// ```toml
+/// [[prompts]]
+/// name = "aws_account_id"
+/// message = "AWS account id?"
+/// pattern = "[0-9]{12}"
+///
+/// [[files]]
+/// path = "lib/vpc.ts"
+/// when = "use_vpc"
+/// rename = "lib/{{ name.kebab }}-vpc.ts"
+///
+/// [[hooks]]
+/// name = "Initialize git"
+/// run = "git init"
+/// ```
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TemplateManifest {
+    /// Extra prompts, asked after the built-in ones
+    #[serde(default)]
+    pub prompts: Vec<PromptDefinition>,
+    /// Conditions and renames of template files
+    #[serde(default)]
+    pub files: Vec<FileRule>,
+    /// Commands run after the project is generated
+    #[serde(default)]
+    pub hooks: Vec<HookDefinition>,
+}
+
+/// A prompt whose answer becomes a template variable.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PromptDefinition {
+    /// Name of the variable in templates
+    pub name: String,
+    /// Question shown to the user
+    pub message: String,
+    #[serde(rename = "type", default)]
+    pub kind: PromptKind,
+    /// Answer used without prompting; a `string` prompt without default must be answered
+    pub default: Option<VariableValue>,
+    /// Choices of a `select` prompt
+    #[serde(default)]
+    pub choices: Vec<String>,
+    /// Regular expression the whole answer of a `string` prompt must match
+    pub pattern: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PromptKind {
+    #[default]
+    String,
+    Bool,
+    Select,
+}
+
+/// Value of a template variable.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum VariableValue {
+    Bool(bool),
+    String(String),
+}
+
+impl fmt::Display for VariableValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VariableValue::Bool(value) => write!(f, "{}", value),
+            VariableValue::String(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+/// Condition and rename of a template file.
+///
+/// A rule for an embedded template (e.g. `jest.config.js`) narrows when the file is
+/// generated; any other path adds a file of the template pack.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FileRule {
+    /// Path of the template relative to the template directory
+    pub path: String,
+    /// MiniJinja expression, e.g. `use_vpc and test_tool != "none"`
+    pub when: Option<String>,
+    /// Path of the file in the project, rendered like a template
+    pub rename: Option<String>,
+}
+
+/// A command run in the project directory after files are written and dependencies installed.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct HookDefinition {
+    /// Shown to the user; the command when not given
+    pub name: Option<String>,
+    /// Shell command, rendered like a template, e.g. `{{ package_manager }} run build`
+    pub run: String,
+    /// MiniJinja expression; the hook only runs when it is true
+    pub when: Option<String>,
+}
+
+impl TemplateManifest {
+    /// Parse and validate a manifest.
+    ///
+    /// ### Parameters
+    /// - `content` - Content of `template.toml`
+    ///
+    /// ### Returns
+    /// - `TemplateManifest` - TemplateManifest
+    pub fn parse(content: &str) -> Result<Self> {
+        let manifest: TemplateManifest = toml::from_str(content)?;
+
+        let mut names = Vec::new();
+        for prompt in &manifest.prompts {
+            let is_identifier = prompt
+                .name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+                && prompt
+                    .name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_');
+            if !is_identifier {
+                anyhow::bail!(
+                    "Prompt name '{}' must contain only letters, digits and underscores",
+                    prompt.name
+                );
+            }
+            if RESERVED_VARIABLES.contains(&prompt.name.as_str()) {
+                anyhow::bail!("Prompt name '{}' is a built-in variable", prompt.name);
+            }
+            if names.contains(&&prompt.name) {
+                anyhow::bail!("Prompt '{}' is declared twice", prompt.name);
+            }
+            names.push(&prompt.name);
+
+            if prompt.kind == PromptKind::Select && prompt.choices.is_empty() {
+                anyhow::bail!("Prompt '{}' has no choices", prompt.name);
+            }
+            if let Some(pattern) = &prompt.pattern {
+                Regex::new(pattern)
+                    .with_context(|| format!("Invalid pattern of prompt '{}'", prompt.name))?;
+            }
+            if let Some(default) = &prompt.default {
+                prompt.check(default).map_err(|reason| {
+                    anyhow::anyhow!("Invalid default of prompt '{}': {}", prompt.name, reason)
+                })?;
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Rule of a template file, if any.
+    pub fn file_rule(&self, template_path: &str) -> Option<&FileRule> {
+        self.files.iter().find(|rule| rule.path == template_path)
+    }
+}
+
+impl PromptDefinition {
+    /// Check an answer against the type, choices and pattern of the prompt.
+    ///
+    /// ### Parameters
+    /// - `value` - Answer
+    ///
+    /// ### Returns
+    /// - `Result<(), String>` - Reason the answer is invalid
+    pub fn check(&self, value: &VariableValue) -> Result<(), String> {
+        match (self.kind, value) {
+            (PromptKind::Bool, VariableValue::Bool(_)) => Ok(()),
+            (PromptKind::Bool, VariableValue::String(_)) => Err("expected true or false".into()),
+            (PromptKind::Select, VariableValue::String(value)) => {
+                if self.choices.contains(value) {
+                    Ok(())
+                } else {
+                    Err(format!("expected one of {}", self.choices.join(", ")))
+                }
+            }
+            (PromptKind::String, VariableValue::String(value)) => match &self.pattern {
+                Some(pattern) => {
+                    // NOTE: the pattern was checked by `TemplateManifest::parse`.
+                    let regex =
+                        Regex::new(&format!("^(?:{})$", pattern)).map_err(|e| e.to_string())?;
+                    if regex.is_match(value) {
+                        Ok(())
+                    } else {
+                        Err(format!("must match {}", pattern))
+                    }
+                }
+                None => Ok(()),
+            },
+            (_, VariableValue::Bool(_)) => Err("expected a string".into()),
+        }
+    }
+
+    /// Parse an answer given as text, e.g. on the command line.
+    pub fn parse_value(&self, raw: &str) -> Result<VariableValue, String> {
+        let value = match self.kind {
+            PromptKind::Bool => match raw {
+                "true" => VariableValue::Bool(true),
+                "false" => VariableValue::Bool(false),
+                _ => return Err("expected true or false".into()),
+            },
+            PromptKind::String | PromptKind::Select => VariableValue::String(raw.to_string()),
+        };
+        self.check(&value)?;
+        Ok(value)
+    }
+
+    /// Answer used when prompts are not shown: the default, `false` for a `bool`
+    /// prompt, or the first choice of a `select` prompt.
+    pub fn fallback(&self) -> Option<VariableValue> {
+        match (&self.default, self.kind) {
+            (Some(default), _) => Some(default.clone()),
+            (None, PromptKind::Bool) => Some(VariableValue::Bool(false)),
+            (None, PromptKind::Select) => self.choices.first().cloned().map(VariableValue::String),
+            (None, PromptKind::String) => None,
+        }
+    }
+}
+
+impl FileRule {
+    /// Whether the `when` condition holds; a rule without condition always applies.
+    pub fn applies(&self, context: &TemplateContext) -> Result<bool> {
+        match &self.when {
+            Some(expression) => evaluate_condition(expression, context)
+                .with_context(|| format!("Invalid condition of file '{}'", self.path)),
+            None => Ok(true),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::{Formatter, Linter, PackageManager, ProjectConfig, TestTool};
+    use std::collections::BTreeMap;
+    use std::path::PathBuf;
+
+    fn prompt(content: &str) -> PromptDefinition {
+        let manifest = TemplateManifest::parse(&format!(
+            "[[prompts]]\nname = \"answer\"\nmessage = \"?\"\n{}",
+            content
+        ))
+        .unwrap();
+        manifest.prompts[0].clone()
+    }
+
+    fn string(value: &str) -> VariableValue {
+        VariableValue::String(value.to_string())
+    }
+
+    #[test]
+    fn rejects_invalid_manifests() {
+        let cases = [
+            ("name = \"1st\"", "must contain only letters"),
+            ("name = \"aws-account\"", "must contain only letters"),
+            ("name = \"\"", "must contain only letters"),
+            ("name = \"test_tool\"", "is a built-in variable"),
+            ("name = \"name\"", "is a built-in variable"),
+            (
+                "name = \"a\"\n[[prompts]]\nname = \"a\"\nmessage = \"?\"",
+                "is declared twice",
+            ),
+            ("name = \"a\"\ntype = \"select\"", "has no choices"),
+            ("name = \"a\"\npattern = \"[0-9\"", "Invalid pattern"),
+            (
+                "name = \"a\"\npattern = \"[0-9]+\"\ndefault = \"x1\"",
+                "Invalid default of prompt 'a': must match [0-9]+",
+            ),
+            (
+                "name = \"a\"\ntype = \"select\"\nchoices = [\"x\", \"y\"]\ndefault = \"z\"",
+                "Invalid default of prompt 'a': expected one of x, y",
+            ),
+            (
+                "name = \"a\"\ntype = \"bool\"\ndefault = \"yes\"",
+                "expected true or false",
+            ),
+            ("name = \"a\"\ndefault = true", "expected a string"),
+            ("name = \"a\"\ntype = \"number\"", "unknown variant"),
+            ("name = \"a\"\nrequired = true", "unknown field"),
+        ];
+        for (prompt, message) in cases {
+            let content = format!("[[prompts]]\nmessage = \"?\"\n{}\n", prompt);
+            let error = format!("{:#}", TemplateManifest::parse(&content).unwrap_err());
+            assert!(error.contains(message), "{}: {}", prompt, error);
+        }
+        assert!(TemplateManifest::parse("[[files]]\npath = \"a\"\nif = \"b\"\n").is_err());
+        assert!(TemplateManifest::parse("[[hooks]]\nname = \"a\"\n").is_err());
+    }
+
+    #[test]
+    fn checks_answers() {
+        let account = prompt("pattern = \"[0-9]{12}\"");
+        assert_eq!(
+            account.parse_value("123456789012"),
+            Ok(string("123456789012"))
+        );
+        // NOTE: the pattern must match the whole answer.
+        for raw in ["a123456789012", "1234567890123", "12345678901", ""] {
+            assert!(account.parse_value(raw).is_err(), "{}", raw);
+        }
+        let alternatives = prompt("pattern = \"dev|prod\"");
+        assert!(alternatives.parse_value("prod").is_ok());
+        assert!(alternatives.parse_value("devprod").is_err());
+
+        let stage = prompt("type = \"select\"\nchoices = [\"dev\", \"prod\"]");
+        assert_eq!(stage.parse_value("prod"), Ok(string("prod")));
+        assert_eq!(
+            stage.parse_value("test"),
+            Err("expected one of dev, prod".to_string())
+        );
+        assert!(stage.check(&VariableValue::Bool(true)).is_err());
+
+        let flag = prompt("type = \"bool\"");
+        assert_eq!(flag.parse_value("true"), Ok(VariableValue::Bool(true)));
+        assert_eq!(flag.parse_value("false"), Ok(VariableValue::Bool(false)));
+        assert!(flag.parse_value("yes").is_err());
+        assert!(flag.check(&string("true")).is_err());
+    }
+
+    #[test]
+    fn falls_back_to_defaults() {
+        assert_eq!(prompt("").fallback(), None);
+        assert_eq!(prompt("default = \"x\"").fallback(), Some(string("x")));
+        assert_eq!(
+            prompt("type = \"bool\"").fallback(),
+            Some(VariableValue::Bool(false))
+        );
+        assert_eq!(
+            prompt("type = \"bool\"\ndefault = true").fallback(),
+            Some(VariableValue::Bool(true))
+        );
+        assert_eq!(
+            prompt("type = \"select\"\nchoices = [\"a\", \"b\"]").fallback(),
+            Some(string("a"))
+        );
+        assert_eq!(
+            prompt("type = \"select\"\nchoices = [\"a\", \"b\"]\ndefault = \"b\"").fallback(),
+            Some(string("b"))
+        );
+    }
+
+    #[test]
+    fn evaluates_file_conditions() {
+        let manifest = TemplateManifest::parse(
+            "[[files]]\npath = \"lib/vpc.ts\"\nwhen = 'use_vpc and test_tool != \"none\"'\n\n[[files]]\npath = \"README.md\"\n\n[[files]]\npath = \"a.ts\"\nwhen = \"missing.attr\"\n\n[[files]]\npath = \"b.ts\"\nwhen = \"use_vpc and\"\n",
+        )
+        .unwrap();
+        let context = |use_vpc: bool, test_tool: TestTool| {
+            TemplateContext::new(&ProjectConfig {
+                target_dir_path: PathBuf::from("."),
+                name: "my-app".to_string(),
+                package_manager: PackageManager::Npm,
+                linter: Linter::None,
+                formatter: Formatter::None,
+                test_tool,
+                package_manager_version: None,
+                variables: BTreeMap::from([("use_vpc".to_string(), VariableValue::Bool(use_vpc))]),
+            })
+        };
+
+        let vpc = manifest.file_rule("lib/vpc.ts").unwrap();
+        assert!(vpc.applies(&context(true, TestTool::Jest)).unwrap());
+        assert!(!vpc.applies(&context(false, TestTool::Jest)).unwrap());
+        assert!(!vpc.applies(&context(true, TestTool::None)).unwrap());
+        let readme = manifest.file_rule("README.md").unwrap();
+        assert!(readme.applies(&context(false, TestTool::None)).unwrap());
+        assert!(manifest.file_rule("lib/other.ts").is_none());
+
+        for path in ["a.ts", "b.ts"] {
+            let error = manifest
+                .file_rule(path)
+                .unwrap()
+                .applies(&context(true, TestTool::Jest))
+                .unwrap_err();
+            assert_eq!(
+                error.to_string(),
+                format!("Invalid condition of file '{}'", path)
+            );
+        }
+    }
+}
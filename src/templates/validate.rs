@@ -0,0 +1,464 @@
+use crate::templates::generate::TemplateFile;
+
+use anyhow::Result;
+use regex::Regex;
+
+/// A problem found in a generated file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    /// Line of the generated file, starting at 1
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+/// Check every generated file before it is written.
+///
+/// Fails on leftover placeholders of the old generator (`%project-name%`, ...),
+/// `.json` files that do not parse, and scripts with unbalanced braces. Template tags
+/// are not searched for: MiniJinja already fails on undefined variables, and output
+/// such as GitHub Actions expressions (`${{ secrets.TOKEN }}`) is legitimate.
+///
+/// ### Parameters
+/// - `files` - Files returned by `generate_template_files`
+pub fn validate_template_files(files: &[TemplateFile]) -> Result<()> {
+    let errors = files
+        .iter()
+        .flat_map(|file| {
+            validate_file(file)
+                .into_iter()
+                .map(move |problem| format_problem(file, &problem))
+        })
+        .collect::<Vec<_>>();
+
+    if !errors.is_empty() {
+        anyhow::bail!("Generated files are invalid:\n  {}", errors.join("\n  "));
+    }
+    Ok(())
+}
+
+/// Find the problems of a generated file.
+///
+/// ### Parameters
+/// - `file` - Generated file
+///
+/// ### Returns
+/// - `Vec<Problem>` - Problems, empty when the file is valid
+pub fn validate_file(file: &TemplateFile) -> Vec<Problem> {
+    let mut problems = find_placeholders(&file.content);
+
+    let extension = file
+        .file_path
+        .rsplit_once('.')
+        .map(|(_, extension)| extension);
+    match extension {
+        Some("json" | "jsonc") => {
+            let content = if is_jsonc(&file.file_path) {
+                strip_jsonc(&file.content)
+            } else {
+                file.content.clone()
+            };
+            if let Err(e) = serde_json::from_str::<serde_json::Value>(&content) {
+                problems.push(Problem {
+                    line: Some(e.line()),
+                    message: format!("invalid JSON: {}", e),
+                });
+            }
+        }
+        Some("js" | "mjs" | "cjs" | "ts" | "mts" | "cts") => {
+            problems.extend(check_delimiters(&file.content));
+        }
+        _ => {}
+    }
+
+    problems
+}
+
+/// Where a problem is, e.g. `lib/%project-name%-stack.ts (lib/my-app-stack.ts), line 3`.
+pub fn format_problem(file: &TemplateFile, problem: &Problem) -> String {
+    let mut location = match &file.template {
+        Some(template) if template != &file.file_path => {
+            format!("{} ({})", template, file.file_path)
+        }
+        _ => file.file_path.clone(),
+    };
+    if let Some(line) = problem.line {
+        location.push_str(&format!(", line {}", line));
+    }
+    format!("{}: {}", location, problem.message)
+}
+
+/// Placeholders the generator used before templates were rendered with MiniJinja.
+const LEGACY_PLACEHOLDERS: &[&str] = &[
+    "project-name",
+    "ProjectName",
+    "test_command",
+    "test_config_file",
+    "test_file",
+    "test_module",
+    "lint_command",
+    "lint_module",
+    "format_command",
+    "format_module",
+];
+
+/// JSON files read by tools that accept comments and trailing commas.
+fn is_jsonc(file_path: &str) -> bool {
+    let file_name = file_path.rsplit('/').next().unwrap_or(file_path);
+    file_name.ends_with(".jsonc")
+        || matches!(file_name, "tsconfig.json" | "jsconfig.json" | "biome.json")
+        || (file_name.starts_with("tsconfig.") && file_name.ends_with(".json"))
+}
+
+/// Turn JSONC into JSON: comments become spaces (keeping line numbers) and trailing
+/// commas are dropped.
+fn strip_jsonc(content: &str) -> String {
+    let chars = content.chars().collect::<Vec<char>>();
+    let mut result = String::with_capacity(content.len());
+    let mut index = 0;
+    while index < chars.len() {
+        match (chars[index], chars.get(index + 1)) {
+            ('"', _) => {
+                result.push('"');
+                index += 1;
+                while index < chars.len() && chars[index] != '"' {
+                    if chars[index] == '\\' && index + 1 < chars.len() {
+                        result.push(chars[index]);
+                        index += 1;
+                    }
+                    result.push(chars[index]);
+                    index += 1;
+                }
+                if index < chars.len() {
+                    result.push('"');
+                }
+            }
+            ('/', Some('/')) => {
+                while index < chars.len() && chars[index] != '\n' {
+                    result.push(' ');
+                    index += 1;
+                }
+                continue;
+            }
+            ('/', Some('*')) => {
+                let end = (index + 2..chars.len())
+                    .find(|&end| chars[end - 1] == '*' && chars[end] == '/' && end > index + 2)
+                    .map_or(chars.len(), |end| end + 1);
+                for &c in &chars[index..end] {
+                    result.push(if c == '\n' { '\n' } else { ' ' });
+                }
+                index = end;
+                continue;
+            }
+            (',', _) => {
+                let next = chars[index + 1..]
+                    .iter()
+                    .copied()
+                    .find(|c| !c.is_whitespace());
+                // NOTE: a comment between the comma and the bracket is not looked through.
+                result.push(if matches!(next, Some('}' | ']')) {
+                    ' '
+                } else {
+                    ','
+                });
+            }
+            (c, _) => result.push(c),
+        }
+        index += 1;
+    }
+    result
+}
+
+/// Placeholders of the old generator left in the content, e.g. `%project-name%`.
+fn find_placeholders(content: &str) -> Vec<Problem> {
+    let placeholder = Regex::new(&format!(
+        "%(?:{})%",
+        LEGACY_PLACEHOLDERS
+            .iter()
+            .map(|name| regex::escape(name))
+            .collect::<Vec<_>>()
+            .join("|")
+    ))
+    .expect("placeholder pattern is valid");
+
+    content
+        .lines()
+        .enumerate()
+        .flat_map(|(index, line)| {
+            placeholder.find_iter(line).map(move |found| Problem {
+                line: Some(index + 1),
+                message: format!("unresolved placeholder `{}`", found.as_str().trim()),
+            })
+        })
+        .collect()
+}
+
+/// Check that braces, brackets and parentheses of a script are balanced.
+///
+/// Strings, template literals, comments and regular expression literals are skipped.
+fn check_delimiters(content: &str) -> Option<Problem> {
+    let chars = content.chars().collect::<Vec<char>>();
+    // NOTE: '`' is an open template literal and '$' a `${` expression inside one.
+    let mut open: Vec<(char, usize)> = Vec::new();
+    let mut line = 1;
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let next = chars.get(index + 1).copied();
+        if c == '\n' {
+            line += 1;
+        }
+
+        if open.last().is_some_and(|(opener, _)| *opener == '`') {
+            match (c, next) {
+                ('\\', _) => index += 1,
+                ('`', _) => {
+                    open.pop();
+                }
+                ('$', Some('{')) => {
+                    open.push(('$', line));
+                    index += 1;
+                }
+                _ => {}
+            }
+            index += 1;
+            continue;
+        }
+
+        match (c, next) {
+            ('/', Some('/')) => {
+                while index + 1 < chars.len() && chars[index + 1] != '\n' {
+                    index += 1;
+                }
+            }
+            ('/', Some('*')) => {
+                let start = line;
+                index += 2;
+                while index < chars.len()
+                    && !(chars[index] == '*' && chars.get(index + 1) == Some(&'/'))
+                {
+                    if chars[index] == '\n' {
+                        line += 1;
+                    }
+                    index += 1;
+                }
+                if index >= chars.len() {
+                    return Some(Problem {
+                        line: Some(start),
+                        message: "unterminated comment".to_string(),
+                    });
+                }
+                index += 1;
+            }
+            ('/', _) if starts_expression(&chars[..index]) => {
+                index += 1;
+                let mut class = false;
+                while index < chars.len() && (class || chars[index] != '/') {
+                    match chars[index] {
+                        '\n' => {
+                            return Some(Problem {
+                                line: Some(line),
+                                message: "unterminated regular expression".to_string(),
+                            })
+                        }
+                        '\\' => index += 1,
+                        '[' => class = true,
+                        ']' => class = false,
+                        _ => {}
+                    }
+                    index += 1;
+                }
+            }
+            ('\'' | '"', _) => {
+                index += 1;
+                while index < chars.len() && chars[index] != c {
+                    if chars[index] == '\n' {
+                        return Some(Problem {
+                            line: Some(line),
+                            message: "unterminated string".to_string(),
+                        });
+                    }
+                    if chars[index] == '\\' {
+                        index += 1;
+                    }
+                    index += 1;
+                }
+            }
+            ('`' | '{' | '(' | '[', _) => open.push((c, line)),
+            ('}' | ')' | ']', _) => {
+                let expected = match c {
+                    '}' => ['{', '$'],
+                    ')' => ['(', '('],
+                    _ => ['[', '['],
+                };
+                match open.pop() {
+                    Some((opener, _)) if expected.contains(&opener) => {}
+                    Some((opener, opened)) => {
+                        return Some(Problem {
+                            line: Some(line),
+                            message: format!(
+                                "unexpected `{}`, `{}` from line {} is not closed",
+                                c,
+                                if opener == '$' { '{' } else { opener },
+                                opened
+                            ),
+                        })
+                    }
+                    None => {
+                        return Some(Problem {
+                            line: Some(line),
+                            message: format!("unmatched `{}`", c),
+                        })
+                    }
+                }
+            }
+            _ => {}
+        }
+        index += 1;
+    }
+
+    open.pop().map(|(opener, opened)| Problem {
+        line: Some(opened),
+        message: match opener {
+            '`' => "unterminated template literal".to_string(),
+            '$' => "`${` is not closed".to_string(),
+            _ => format!("`{}` is not closed", opener),
+        },
+    })
+}
+
+/// Whether an expression can start after the given script, so that a `/` begins a
+/// regular expression literal rather than a division.
+fn starts_expression(before: &[char]) -> bool {
+    const KEYWORDS: [&str; 14] = [
+        "return",
+        "typeof",
+        "instanceof",
+        "in",
+        "of",
+        "new",
+        "delete",
+        "void",
+        "throw",
+        "case",
+        "do",
+        "else",
+        "yield",
+        "await",
+    ];
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_' || *c == '$';
+    let trimmed = before
+        .iter()
+        .rposition(|c| !c.is_whitespace())
+        .map_or(&before[..0], |end| &before[..=end]);
+    match trimmed.last() {
+        None => true,
+        Some(')' | ']' | '\'' | '"' | '`') => false,
+        Some(c) if is_word(c) => {
+            let start = trimmed
+                .iter()
+                .rposition(|c| !is_word(c))
+                .map_or(0, |index| index + 1);
+            let word = trimmed[start..].iter().collect::<String>();
+            KEYWORDS.contains(&word.as_str())
+        }
+        Some(_) => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(file_path: &str, content: &str) -> TemplateFile {
+        TemplateFile {
+            file_path: file_path.to_string(),
+            content: content.to_string(),
+            template: None,
+        }
+    }
+
+    #[test]
+    fn accepts_valid_files() {
+        let script = "// }\n/* ) */\nconst a = { b: [1, 2], c: '}', d: \"]\" };\nconst e = `${f({ g: 1 })} }`;\n";
+        assert_eq!(validate_file(&file("lib/a.ts", script)), vec![]);
+        let regex = "s.replace(/[{(]/g, '');\nconst a = x.split(/\\)|[/]/);\nconst b = (c) / 2 / (d);\nreturn /}/.test(e);\n";
+        assert_eq!(validate_file(&file("lib/b.ts", regex)), vec![]);
+        assert_eq!(
+            validate_file(&file("cdk.json", "{ \"app\": \"x\" }")),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn finds_placeholders() {
+        let problems = validate_file(&file(
+            "README.md",
+            "# %project-name%\n\nRun %test_command%.\n",
+        ));
+        let lines = problems
+            .iter()
+            .map(|problem| problem.line)
+            .collect::<Vec<_>>();
+        assert_eq!(lines, vec![Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn accepts_text_resembling_placeholders() {
+        let workflow = "steps:\n  - run: npm publish\n    env:\n      TOKEN: ${{ secrets.TOKEN }}\n      IF: ${{ github.event_name == 'push' }}\n";
+        assert_eq!(
+            validate_file(&file(".github/workflows/ci.yml", workflow)),
+            vec![]
+        );
+        let readme = "![check](https://img.shields.io/badge/%E2%9C%93-ok-green)\n{% raw %}\n";
+        assert_eq!(validate_file(&file("README.md", readme)), vec![]);
+    }
+
+    #[test]
+    fn accepts_comments_in_jsonc_files() {
+        let tsconfig = "{\n  // Emit nothing\n  \"compilerOptions\": {\n    /* \"a\": 1 */\n    \"noEmit\": true,\n    \"paths\": { \"~/*\": [\"./*\"] },\n  },\n}\n";
+        assert_eq!(validate_file(&file("tsconfig.json", tsconfig)), vec![]);
+        assert_eq!(validate_file(&file("biome.json", tsconfig)), vec![]);
+
+        let problems = validate_file(&file("package.json", tsconfig));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].line, Some(2));
+        let problems = validate_file(&file("tsconfig.json", "{\n  // a\n  \"b\": 1 2\n}\n"));
+        assert_eq!(problems[0].line, Some(3));
+    }
+
+    #[test]
+    fn finds_unbalanced_delimiters() {
+        let cases = [
+            ("a({\n", Some(1), "`{` is not closed"),
+            (
+                "a(\n}\n",
+                Some(2),
+                "unexpected `}`, `(` from line 1 is not closed",
+            ),
+            ("a;\n)\n", Some(2), "unmatched `)`"),
+            (
+                "const a = `${b`;\n",
+                Some(1),
+                "unterminated template literal",
+            ),
+            ("a(/[(]/;\n", Some(1), "`(` is not closed"),
+            (
+                "const a = /(;\n",
+                Some(1),
+                "unterminated regular expression",
+            ),
+        ];
+        for (script, line, message) in cases {
+            assert_eq!(
+                check_delimiters(script),
+                Some(Problem {
+                    line,
+                    message: message.to_string()
+                }),
+                "{:?}",
+                script
+            );
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use crate::cli::init::ProjectConfig;
+use crate::templates::generate::TemplateFile;
+
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// Files written into the target directory by `write_template_files`.
+#[derive(Debug)]
+pub struct WrittenProject {
+    pub target_dir: PathBuf,
+    /// Whether the target directory was created by ts-cdk
+    pub created_target_dir: bool,
+    /// Files that did not exist before, relative to the target directory
+    pub created_files: Vec<String>,
+    /// Directories that did not exist before, outermost first: missing parents of the
+    /// target directory, or directories created inside an existing one
+    pub created_dirs: Vec<PathBuf>,
+}
+
+impl WrittenProject {
+    /// Remove what was written: the whole target directory when ts-cdk created it,
+    /// otherwise only the files and directories that did not exist before.
+    pub fn remove(&self) -> Result<()> {
+        if self.created_target_dir {
+            std::fs::remove_dir_all(&self.target_dir)
+                .with_context(|| format!("Failed to remove {}", self.target_dir.display()))?;
+        } else {
+            for file_path in &self.created_files {
+                let path = self.target_dir.join(file_path);
+                std::fs::remove_file(&path)
+                    .with_context(|| format!("Failed to remove {}", path.display()))?;
+            }
+        }
+        remove_dirs(&self.created_dirs);
+        Ok(())
+    }
+}
+
+/// Write template files into the target directory.
+///
+/// Every file is first written into a staging directory next to the target and only
+/// moved into place once all of them were written. On error, nothing is left behind,
+/// including missing parents of the target directory, and files that were overwritten
+/// are restored.
+///
+/// ### Parameters
+/// - `config` - ProjectConfig
+/// - `files` - Files to write
+///
+/// ### Returns
+/// - `WrittenProject` - What was written
+pub fn write_template_files(
+    config: &ProjectConfig,
+    files: &[TemplateFile],
+) -> Result<WrittenProject> {
+    let target_dir = config.target_dir_path.clone();
+    let parent_dir = match target_dir.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent.to_path_buf(),
+        _ => PathBuf::from("."),
+    };
+    let created_dirs = missing_dirs(&parent_dir);
+    std::fs::create_dir_all(&parent_dir)
+        .with_context(|| format!("Failed to create {}", parent_dir.display()))?;
+
+    let written = write_files(&target_dir, &parent_dir, files);
+    match written {
+        Ok(mut project) => {
+            if project.created_target_dir {
+                project.created_dirs = created_dirs;
+            }
+            Ok(project)
+        }
+        Err(e) => {
+            remove_dirs(&created_dirs);
+            Err(e)
+        }
+    }
+}
+
+/// Stage the files next to the target directory and move them into place.
+fn write_files(
+    target_dir: &Path,
+    parent_dir: &Path,
+    files: &[TemplateFile],
+) -> Result<WrittenProject> {
+    let target_dir = target_dir.to_path_buf();
+    let staging_dir = tempfile::Builder::new()
+        .prefix(".ts-cdk-staging-")
+        .tempdir_in(parent_dir)
+        .with_context(|| {
+            format!(
+                "Failed to create a staging directory in {}",
+                parent_dir.display()
+            )
+        })?;
+    let staged_dir = staging_dir.path().join("project");
+
+    for file in files {
+        let staged_path = staged_dir.join(&file.file_path);
+        if let Some(parent) = staged_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&staged_path, &file.content)
+            .with_context(|| format!("Failed to write {}", file.file_path))?;
+    }
+
+    // NOTE: a new target directory is moved into place in a single step.
+    if !target_dir.exists() {
+        std::fs::rename(&staged_dir, &target_dir)
+            .with_context(|| format!("Failed to create {}", target_dir.display()))?;
+        return Ok(WrittenProject {
+            target_dir,
+            created_target_dir: true,
+            created_files: files.iter().map(|file| file.file_path.clone()).collect(),
+            created_dirs: Vec::new(),
+        });
+    }
+
+    let backup_dir = staging_dir.path().join("backup");
+    let mut moves = Vec::new();
+    for file in files {
+        match move_into_place(&staged_dir, &backup_dir, &target_dir, &file.file_path) {
+            Ok(applied) => moves.push(applied),
+            Err(e) => {
+                rollback(&target_dir, &backup_dir, &moves);
+                return Err(e);
+            }
+        }
+    }
+
+    Ok(WrittenProject {
+        target_dir,
+        created_target_dir: false,
+        created_files: moves
+            .iter()
+            .filter(|applied| !applied.backed_up)
+            .map(|applied| applied.file_path.clone())
+            .collect(),
+        created_dirs: moves
+            .into_iter()
+            .flat_map(|applied| applied.created_dirs)
+            .collect(),
+    })
+}
+
+#[derive(Debug)]
+struct AppliedMove {
+    file_path: String,
+    /// Whether an existing file was moved to the backup directory
+    backed_up: bool,
+    /// Directories created in the target directory, outermost first
+    created_dirs: Vec<PathBuf>,
+}
+
+fn move_into_place(
+    staged_dir: &Path,
+    backup_dir: &Path,
+    target_dir: &Path,
+    file_path: &str,
+) -> Result<AppliedMove> {
+    let target_path = target_dir.join(file_path);
+    let mut applied = AppliedMove {
+        file_path: file_path.to_string(),
+        backed_up: false,
+        created_dirs: Vec::new(),
+    };
+
+    if let Some(parent) = target_path.parent() {
+        let created_dirs = missing_dirs(parent);
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            remove_dirs(&created_dirs);
+            return Err(e).with_context(|| format!("Failed to create {}", parent.display()));
+        }
+        applied.created_dirs = created_dirs;
+    }
+
+    if target_path.exists() {
+        let backup_path = backup_dir.join(file_path);
+        if let Some(parent) = backup_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::rename(&target_path, &backup_path)
+            .with_context(|| format!("Failed to replace {}", target_path.display()))?;
+        applied.backed_up = true;
+    }
+
+    if let Err(e) = std::fs::rename(staged_dir.join(file_path), &target_path) {
+        rollback(target_dir, backup_dir, std::slice::from_ref(&applied));
+        return Err(e).with_context(|| format!("Failed to write {}", target_path.display()));
+    }
+
+    Ok(applied)
+}
+
+/// Undo applied moves in reverse order. Errors are ignored so that as much as possible is restored.
+fn rollback(target_dir: &Path, backup_dir: &Path, moves: &[AppliedMove]) {
+    for applied in moves.iter().rev() {
+        let target_path = target_dir.join(&applied.file_path);
+        let _ = std::fs::remove_file(&target_path);
+        if applied.backed_up {
+            let _ = std::fs::rename(backup_dir.join(&applied.file_path), &target_path);
+        }
+        remove_dirs(&applied.created_dirs);
+    }
+}
+
+/// Ancestors of `dir` (itself included) that do not exist yet, outermost first.
+fn missing_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut missing_dirs = dir
+        .ancestors()
+        .take_while(|dir| !dir.as_os_str().is_empty() && !dir.exists())
+        .map(Path::to_path_buf)
+        .collect::<Vec<_>>();
+    missing_dirs.reverse();
+    missing_dirs
+}
+
+/// Remove directories created by ts-cdk, innermost first.
+///
+/// A directory that is no longer empty (e.g. holding files the user added since) is kept.
+fn remove_dirs(dirs: &[PathBuf]) {
+    for dir in dirs.iter().rev() {
+        let _ = std::fs::remove_dir(dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::init::{Formatter, Linter, PackageManager, TestTool};
+
+    fn config(target_dir_path: PathBuf) -> ProjectConfig {
+        ProjectConfig {
+            target_dir_path,
+            name: "my-app".to_string(),
+            package_manager: PackageManager::Npm,
+            linter: Linter::EsLint,
+            formatter: Formatter::Prettier,
+            test_tool: TestTool::Jest,
+            package_manager_version: None,
+            variables: Default::default(),
+        }
+    }
+
+    fn file(file_path: &str, content: &str) -> TemplateFile {
+        TemplateFile {
+            file_path: file_path.to_string(),
+            content: content.to_string(),
+            template: None,
+        }
+    }
+
+    /// Paths under `dir` with the content of each file, `None` for directories.
+    fn snapshot(dir: &Path) -> Vec<(PathBuf, Option<String>)> {
+        let mut entries = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(&current).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path.clone());
+                    entries.push((path, None));
+                } else {
+                    let content = std::fs::read_to_string(&path).unwrap();
+                    entries.push((path, Some(content)));
+                }
+            }
+        }
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn restores_an_existing_target_directory_on_failure() {
+        let root = tempfile::tempdir().unwrap();
+        let target_dir = root.path().join("my-app");
+        std::fs::create_dir(&target_dir).unwrap();
+        std::fs::write(target_dir.join("README.md"), "mine").unwrap();
+        // NOTE: `lib` is a file, so `lib/my-app-stack.ts` cannot be written.
+        std::fs::write(target_dir.join("lib"), "not a directory").unwrap();
+        let before = snapshot(root.path());
+
+        let files = [
+            file("bin/my-app.ts", "new App();"),
+            file("README.md", "# my-app"),
+            file("lib/my-app-stack.ts", "export {};"),
+        ];
+        assert!(write_template_files(&config(target_dir), &files).is_err());
+        assert_eq!(snapshot(root.path()), before);
+    }
+
+    #[test]
+    fn removes_created_parent_directories_on_failure() {
+        let root = tempfile::tempdir().unwrap();
+        let target_dir = root.path().join("a").join("b").join("my-app");
+
+        let files = [file("bin", "a file"), file("bin/my-app.ts", "new App();")];
+        assert!(write_template_files(&config(target_dir), &files).is_err());
+        assert_eq!(snapshot(root.path()), vec![]);
+    }
+
+    #[test]
+    fn removes_created_files_and_directories() {
+        let root = tempfile::tempdir().unwrap();
+        let target_dir = root.path().join("my-app");
+        std::fs::create_dir(&target_dir).unwrap();
+        std::fs::write(target_dir.join("README.md"), "mine").unwrap();
+        let before = snapshot(root.path());
+
+        let files = [file("bin/my-app.ts", "new App();"), file("test/a/b.ts", "")];
+        let project = write_template_files(&config(target_dir), &files).unwrap();
+        project.remove().unwrap();
+        assert_eq!(snapshot(root.path()), before);
+    }
+}
@@ -0,0 +1,28 @@
+import * as cdk from 'aws-cdk-lib';
+import { Template } from 'aws-cdk-lib/assertions';
+{% if test_tool == "vitest" %}
+import { describe, expect, test } from 'vitest';
+{% endif %}
+import { {{ name.pascal }} } from '../../lib/constructs/{{ name.kebab }}';
+
+describe('{{ name.pascal }}', () => {
+  test('uses the given removal policy', () => {
+    const stack = new cdk.Stack();
+    // WHEN
+    const construct = new {{ name.pascal }}(stack, '{{ name.pascal }}', {
+      removalPolicy: cdk.RemovalPolicy.DESTROY,
+    });
+    // THEN
+    expect(construct.removalPolicy).toBe(cdk.RemovalPolicy.DESTROY);
+  });
+
+  test('synthesizes', () => {
+    const stack = new cdk.Stack();
+    // WHEN
+    new {{ name.pascal }}(stack, '{{ name.pascal }}');
+    // THEN
+    const template = Template.fromStack(stack);
+
+    expect(template.toJSON()).toBeDefined();
+  });
+});
@@ -0,0 +1,27 @@
+import * as cdk from 'aws-cdk-lib';
+import { Construct } from 'constructs';
+
+export interface {{ name.pascal }}Props {
+  /**
+   * Removal policy of the resources in this construct.
+   *
+   * @default cdk.RemovalPolicy.RETAIN
+   */
+  readonly removalPolicy?: cdk.RemovalPolicy;
+}
+
+export class {{ name.pascal }} extends Construct {
+  /**
+   * Removal policy applied to the resources of this construct.
+   */
+  public readonly removalPolicy: cdk.RemovalPolicy;
+
+  constructor(scope: Construct, id: string, props: {{ name.pascal }}Props = {}) {
+    super(scope, id);
+
+    this.removalPolicy = props.removalPolicy ?? cdk.RemovalPolicy.RETAIN;
+
+    // The resources of the construct go here, e.g.
+    // new s3.Bucket(this, 'Bucket', { removalPolicy: this.removalPolicy });
+  }
+}
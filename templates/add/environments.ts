@@ -0,0 +1,21 @@
+import type { Environment } from 'aws-cdk-lib';
+
+export type EnvironmentName = 'dev' | 'stg' | 'prod';
+
+export interface EnvironmentConfig {
+  /**
+   * Name of the environment, used as the id of its stage.
+   */
+  readonly name: EnvironmentName;
+  /**
+   * AWS account and region the environment is deployed to.
+   */
+  readonly env: Required<Pick<Environment, 'account' | 'region'>>;
+}
+
+// Replace the account ids and regions with those of your environments.
+export const environments: Record<EnvironmentName, EnvironmentConfig> = {
+  dev: { name: 'dev', env: { account: '111111111111', region: 'us-east-1' } },
+  stg: { name: 'stg', env: { account: '222222222222', region: 'us-east-1' } },
+  prod: { name: 'prod', env: { account: '333333333333', region: 'us-east-1' } },
+};
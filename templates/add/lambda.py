@@ -0,0 +1,10 @@
+import json
+
+
+def handler(event, context):
+    print("event", json.dumps(event))
+
+    return {
+        "statusCode": 200,
+        "body": json.dumps({"message": "Hello from {{ name.kebab }}"}),
+    }
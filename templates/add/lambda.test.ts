@@ -0,0 +1,12 @@
+import type { APIGatewayProxyEvent } from 'aws-lambda';
+{% if test_tool == "vitest" %}
+import { expect, test } from 'vitest';
+{% endif %}
+import { handler } from '../../lambda/{{ name.kebab }}';
+
+test('{{ name.kebab }} handler returns 200', async () => {
+  // WHEN
+  const result = await handler({ path: '/' } as APIGatewayProxyEvent);
+  // THEN
+  expect(result.statusCode).toBe(200);
+});
@@ -0,0 +1,10 @@
+import type { APIGatewayProxyEvent, APIGatewayProxyResult } from 'aws-lambda';
+
+export const handler = async (event: APIGatewayProxyEvent): Promise<APIGatewayProxyResult> => {
+  console.log('event', JSON.stringify(event));
+
+  return {
+    statusCode: 200,
+    body: JSON.stringify({ message: 'Hello from {{ name.kebab }}' }),
+  };
+};
@@ -0,0 +1,21 @@
+import importlib.util
+import unittest
+from pathlib import Path
+
+HANDLER_PATH = Path(__file__).resolve().parents[2] / "lambda" / "{{ name.kebab }}" / "index.py"
+
+spec = importlib.util.spec_from_file_location("{{ name.snake }}_index", HANDLER_PATH)
+index = importlib.util.module_from_spec(spec)
+spec.loader.exec_module(index)
+
+
+class HandlerTest(unittest.TestCase):
+    def test_returns_200(self):
+        # WHEN
+        result = index.handler({"path": "/"}, None)
+        # THEN
+        self.assertEqual(result["statusCode"], 200)
+
+
+if __name__ == "__main__":
+    unittest.main()
@@ -0,0 +1,11 @@
+#!/usr/bin/env node
+import 'source-map-support/register';
+import * as cdk from 'aws-cdk-lib';
+import { environments } from '../config/environments';
+import { {{ name.pascal }}Stack } from '../lib/{{ name.kebab }}-stack';
+
+const app = new cdk.App();
+
+// The pipeline deploys {{ stage.class_name }} to every environment and updates itself on each push.
+// It lives in the dev account; deploy it once with `cdk deploy {{ name.pascal }}Stack`.
+new {{ name.pascal }}Stack(app, '{{ name.pascal }}Stack', { env: environments.dev.env });
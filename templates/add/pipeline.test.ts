@@ -0,0 +1,17 @@
+import * as cdk from 'aws-cdk-lib';
+import { Template } from 'aws-cdk-lib/assertions';
+{% if test_tool == "vitest" %}
+import { test } from 'vitest';
+{% endif %}
+import { environments } from '../config/environments';
+import { {{ name.pascal }}Stack } from '../lib/{{ name.kebab }}-stack';
+
+test('{{ name.pascal }}Stack defines a pipeline', () => {
+  const app = new cdk.App();
+  // WHEN
+  const stack = new {{ name.pascal }}Stack(app, 'MyTestStack', { env: environments.dev.env });
+  // THEN
+  const template = Template.fromStack(stack);
+
+  template.resourceCountIs('AWS::CodePipeline::Pipeline', 1);
+});
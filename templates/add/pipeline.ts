@@ -0,0 +1,46 @@
+import * as cdk from 'aws-cdk-lib';
+{% if source == "codecommit" %}
+import * as codecommit from 'aws-cdk-lib/aws-codecommit';
+{% endif %}
+import * as pipelines from 'aws-cdk-lib/pipelines';
+import { Construct } from 'constructs';
+import { environments } from '../config/environments';
+import { {{ stage.class_name }} } from './{{ stage.module }}';
+
+export class {{ name.pascal }}Stack extends cdk.Stack {
+  constructor(scope: Construct, id: string, props?: cdk.StackProps) {
+    super(scope, id, props);
+
+{% if source == "codecommit" %}
+    const repository = codecommit.Repository.fromRepositoryName(this, 'Repository', '{{ repository }}');
+
+{% endif %}
+    const pipeline = new pipelines.CodePipeline(this, '{{ name.pascal }}', {
+      // Each environment may live in its own account.
+      crossAccountKeys: true,
+      synth: new pipelines.ShellStep('Synth', {
+{% if source == "codecommit" %}
+        input: pipelines.CodePipelineSource.codeCommit(repository, '{{ branch }}'),
+{% else %}
+        input: pipelines.CodePipelineSource.connection('{{ repository }}', '{{ branch }}', {
+          connectionArn: '{{ connection_arn }}',
+        }),
+{% endif %}
+{% if install_commands %}
+        installCommands: [{% for command in install_commands %}'{{ command }}'{% if not loop.last %}, {% endif %}{% endfor %}],
+{% endif %}
+        commands: [{% for command in commands %}'{{ command }}'{% if not loop.last %}, {% endif %}{% endfor %}],
+      }),
+    });
+
+    for (const environment of Object.values(environments)) {
+      const stage = new {{ stage.class_name }}(this, environment.name, { env: environment.env });
+      pipeline.addStage(stage, {
+        pre:
+          environment.name === 'prod'
+            ? [new pipelines.ManualApprovalStep(`Promote to ${environment.name}`)]
+            : [],
+      });
+    }
+  }
+}
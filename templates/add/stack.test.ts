@@ -0,0 +1,16 @@
+import * as cdk from 'aws-cdk-lib';
+import { Template } from 'aws-cdk-lib/assertions';
+{% if test_tool == "vitest" %}
+import { expect, test } from 'vitest';
+{% endif %}
+import { {{ name.pascal }}Stack } from '../lib/{{ name.kebab }}-stack';
+
+test('{{ name.pascal }}Stack synthesizes', () => {
+  const app = new cdk.App();
+  // WHEN
+  const stack = new {{ name.pascal }}Stack(app, 'MyTestStack');
+  // THEN
+  const template = Template.fromStack(stack);
+
+  expect(template.toJSON()).toBeDefined();
+});
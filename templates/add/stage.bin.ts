@@ -0,0 +1,12 @@
+#!/usr/bin/env node
+import 'source-map-support/register';
+import * as cdk from 'aws-cdk-lib';
+import { environments } from '../config/environments';
+import { {{ name.pascal }}Stage } from '../lib/{{ name.kebab }}-stage';
+
+const app = new cdk.App();
+
+// One stage per environment: `cdk deploy 'dev/*'` deploys the stacks of dev.
+for (const environment of Object.values(environments)) {
+  new {{ name.pascal }}Stage(app, environment.name, { env: environment.env });
+}
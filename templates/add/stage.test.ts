@@ -0,0 +1,15 @@
+import * as cdk from 'aws-cdk-lib';
+{% if test_tool == "vitest" %}
+import { expect, test } from 'vitest';
+{% endif %}
+import { {{ name.pascal }}Stage } from '../lib/{{ name.kebab }}-stage';
+
+test('{{ name.pascal }}Stage synthesizes its stacks', () => {
+  const app = new cdk.App();
+  // WHEN
+  const stage = new {{ name.pascal }}Stage(app, 'Test');
+  // THEN
+  const assembly = stage.synth();
+
+  expect(assembly.stacks).not.toHaveLength(0);
+});
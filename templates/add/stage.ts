@@ -0,0 +1,15 @@
+import * as cdk from 'aws-cdk-lib';
+import { Construct } from 'constructs';
+{% for stack in stacks %}
+import { {{ stack.class_name }} } from './{{ stack.module }}';
+{% endfor %}
+
+export class {{ name.pascal }}Stage extends cdk.Stage {
+  constructor(scope: Construct, id: string, props?: cdk.StageProps) {
+    super(scope, id, props);
+
+{% for stack in stacks %}
+    new {{ stack.class_name }}(this, '{{ stack.class_name }}');
+{% endfor %}
+  }
+}
@@ -1,10 +1,10 @@
 #!/usr/bin/env node
 import 'source-map-support/register';
 import * as cdk from 'aws-cdk-lib';
-import { %ProjectName%Stack } from '../lib/%project-name%-stack';
+import { {{ name.pascal }}Stack } from '../lib/{{ name.kebab }}-stack';
 
 const app = new cdk.App();
-new %ProjectName%Stack(app, '%ProjectName%Stack', {
+new {{ name.pascal }}Stack(app, '{{ name.pascal }}Stack', {
   /* If you don't specify 'env', this stack will be environment-agnostic.
    * Account/Region-dependent features and context lookups will not work,
    * but a single synthesized template can be deployed anywhere. */
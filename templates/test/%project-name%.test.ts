@@ -1,13 +1,13 @@
 // import * as cdk from 'aws-cdk-lib';
 // import { Template } from 'aws-cdk-lib/assertions';
-// import * as %ProjectName% from '../lib/%project-name%-stack';
+// import * as {{ name.pascal }} from '../lib/{{ name.kebab }}-stack';
 
 // example test. To run these tests, uncomment this file along with the
-// example resource in lib/%project-name%-stack.ts
+// example resource in lib/{{ name.kebab }}-stack.ts
 test('SQS Queue Created', () => {
 //   const app = new cdk.App();
 //     // WHEN
-//   const stack = new %ProjectName%.%ProjectName%Stack(app, 'MyTestStack');
+//   const stack = new {{ name.pascal }}.{{ name.pascal }}Stack(app, 'MyTestStack');
 //     // THEN
 //   const template = Template.fromStack(stack);
 